@@ -0,0 +1,99 @@
+// Comparison of the batched transformations with the one vector at a time
+// path, run with
+//     cargo bench --bench batch
+//
+// Results measured on an x86_64 machine (release build, 50000 vectors,
+// nanoseconds per vector):
+//     operation     scalar    batch
+//     mul             5.31     3.77
+//     inv_mul        10.29     3.80
+//     dual_mul        5.80     3.86
+//     trans_mul      10.95     3.82
+//
+// The batched loops are vectorized and copy the transformation entries only
+// once, so that all four operations run at the same speed, which is then
+// bounded by the memory bandwidth at this batch size.
+
+extern crate sva;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use sva::sva::*;
+
+const NR_VECTORS: usize = 50_000;
+const NR_RUNS: usize = 200;
+
+fn time_per_vector<F: FnMut()>(mut f: F) -> f64 {
+    f();
+    let start = Instant::now();
+    for _ in 0..NR_RUNS {
+        f();
+    }
+    start.elapsed().as_secs_f64() * 1e9 / (NR_RUNS * NR_VECTORS) as f64
+}
+
+fn main() {
+    let transform = PTransform::from_quat_vec(
+        Quat::from_scaled_axis(Vec3::new(0.3, -1.2, 0.7)),
+        Vec3::new(0.5, 1., -2.),
+    );
+    let motions: Vec<MotionVector> = (0..NR_VECTORS)
+        .map(|_| MotionVector::from_vector(Vec6::new_random()))
+        .collect();
+    let forces: Vec<ForceVector> = (0..NR_VECTORS)
+        .map(|_| ForceVector::from_vector(Vec6::new_random()))
+        .collect();
+    let motion_batch = MotionVectorBatch::from_vectors(&motions);
+    let force_batch = ForceVectorBatch::from_vectors(&forces);
+
+    let mut motion_output = vec![MotionVector::zero(); NR_VECTORS];
+    let mut force_output = vec![ForceVector::zero(); NR_VECTORS];
+    let mut motion_batch_output = MotionVectorBatch::new();
+    let mut force_batch_output = ForceVectorBatch::new();
+
+    println!("{:<12}{:>10}{:>10}", "operation", "scalar", "batch");
+
+    let scalar = time_per_vector(|| {
+        for (out, mv) in motion_output.iter_mut().zip(&motions) {
+            *out = black_box(&transform) * mv;
+        }
+    });
+    let batch = time_per_vector(|| {
+        black_box(&transform).batch_mul_into(&motion_batch, &mut motion_batch_output)
+    });
+    println!("{:<12}{:>10.2}{:>10.2}", "mul", scalar, batch);
+
+    let scalar = time_per_vector(|| {
+        for (out, mv) in motion_output.iter_mut().zip(&motions) {
+            *out = black_box(&transform).inv_mul(mv);
+        }
+    });
+    let batch = time_per_vector(|| {
+        black_box(&transform).batch_inv_mul_into(&motion_batch, &mut motion_batch_output)
+    });
+    println!("{:<12}{:>10.2}{:>10.2}", "inv_mul", scalar, batch);
+
+    let scalar = time_per_vector(|| {
+        for (out, fv) in force_output.iter_mut().zip(&forces) {
+            *out = black_box(&transform).dual_mul(fv);
+        }
+    });
+    let batch = time_per_vector(|| {
+        black_box(&transform).batch_dual_mul_into(&force_batch, &mut force_batch_output)
+    });
+    println!("{:<12}{:>10.2}{:>10.2}", "dual_mul", scalar, batch);
+
+    let scalar = time_per_vector(|| {
+        for (out, fv) in force_output.iter_mut().zip(&forces) {
+            *out = black_box(&transform).trans_mul(fv);
+        }
+    });
+    let batch = time_per_vector(|| {
+        black_box(&transform).batch_trans_mul_into(&force_batch, &mut force_batch_output)
+    });
+    println!("{:<12}{:>10.2}{:>10.2}", "trans_mul", scalar, batch);
+
+    black_box((&motion_output, &force_output));
+    black_box((&motion_batch_output, &force_batch_output));
+}
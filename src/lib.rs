@@ -1,5 +1,14 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Lets the modules import from std::{fmt, ops, ...} in both configurations
+#[cfg(not(feature = "std"))]
+extern crate core as std;
+extern crate libm;
 extern crate nalgebra;
 extern crate num_traits;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 pub mod sva;
+#[cfg(feature = "std")]
 mod tests;
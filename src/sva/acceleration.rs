@@ -0,0 +1,38 @@
+use sva::*;
+
+// Spatial accelerations are the time derivative of the velocity field at a
+// fixed point while classical accelerations follow a body point, the two
+// linear parts differ by angular × linear velocity.
+pub fn classical_acceleration(
+    velocity: &MotionVector,
+    acceleration: &MotionVector,
+) -> MotionVector {
+    MotionVector::from_vectors(
+        acceleration.angular,
+        acceleration.linear + velocity.angular.cross(&velocity.linear),
+    )
+}
+
+pub fn spatial_acceleration(velocity: &MotionVector, acceleration: &MotionVector) -> MotionVector {
+    MotionVector::from_vectors(
+        acceleration.angular,
+        acceleration.linear - velocity.angular.cross(&velocity.linear),
+    )
+}
+
+// Velocity of a body point, given in the frame the velocity is expressed in
+pub fn point_velocity(velocity: &MotionVector, point: &Vec3) -> Vec3 {
+    PTransform::from_vec(*point).linear_mul(velocity)
+}
+
+// Classical acceleration of a body point, given in the frame the velocity
+// and acceleration are expressed in
+#[allow(non_snake_case)]
+pub fn point_acceleration(
+    velocity: &MotionVector,
+    acceleration: &MotionVector,
+    point: &Vec3,
+) -> Vec3 {
+    let X_o_p = PTransform::from_vec(*point);
+    classical_acceleration(&(&X_o_p * velocity), &(&X_o_p * acceleration)).linear
+}
@@ -0,0 +1,174 @@
+use std::ops;
+
+use sva::*;
+
+// Linear operators on spatial vectors, applied without building their 6x6
+// matrix. matrix() returns the matrix acting on the [angular; linear]
+// (resp. [couple; force]) coordinates.
+pub trait SpatialOperator {
+    fn matrix(&self) -> Mat6;
+}
+
+// Spatial cross product operator ad_v = v x acting on motion vectors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ad {
+    mv: MotionVector,
+}
+
+// Dual cross product operator ad*_v = v x* = -ad_v^T acting on force vectors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdDual {
+    mv: MotionVector,
+}
+
+pub fn ad(mv: MotionVector) -> Ad {
+    Ad { mv }
+}
+
+pub fn ad_dual(mv: MotionVector) -> AdDual {
+    AdDual { mv }
+}
+
+impl Ad {
+    pub fn motion(&self) -> MotionVector {
+        self.mv
+    }
+
+    pub fn dual(&self) -> AdDual {
+        ad_dual(self.mv)
+    }
+}
+
+impl AdDual {
+    pub fn motion(&self) -> MotionVector {
+        self.mv
+    }
+}
+
+impl SpatialOperator for Ad {
+    fn matrix(&self) -> Mat6 {
+        vector6_to_cross_matrix(&self.mv.vector())
+    }
+}
+
+impl SpatialOperator for AdDual {
+    fn matrix(&self) -> Mat6 {
+        vector6_to_cross_dual_matrix(&self.mv.vector())
+    }
+}
+
+impl SpatialOperator for RBInertia {
+    fn matrix(&self) -> Mat6 {
+        RBInertia::matrix(self)
+    }
+}
+
+impl ops::Mul<MotionVector> for Ad {
+    type Output = MotionVector;
+
+    fn mul(self, mv: MotionVector) -> MotionVector {
+        self.mv.cross(mv)
+    }
+}
+
+impl ops::Mul<ForceVector> for AdDual {
+    type Output = ForceVector;
+
+    fn mul(self, fv: ForceVector) -> ForceVector {
+        self.mv.cross_dual(fv)
+    }
+}
+
+// Lazy product left * right of two operators, right being applied first
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Compose<L, R> {
+    pub left: L,
+    pub right: R,
+}
+
+pub fn compose<L, R>(left: L, right: R) -> Compose<L, R> {
+    Compose { left, right }
+}
+
+impl<L: SpatialOperator, R: SpatialOperator> SpatialOperator for Compose<L, R> {
+    fn matrix(&self) -> Mat6 {
+        self.left.matrix() * self.right.matrix()
+    }
+}
+
+impl<L, R> ops::Mul<MotionVector> for Compose<L, R>
+where
+    R: ops::Mul<MotionVector>,
+    L: ops::Mul<R::Output>,
+{
+    type Output = L::Output;
+
+    fn mul(self, mv: MotionVector) -> L::Output {
+        self.left * (self.right * mv)
+    }
+}
+
+impl<L, R> ops::Mul<ForceVector> for Compose<L, R>
+where
+    R: ops::Mul<ForceVector>,
+    L: ops::Mul<R::Output>,
+{
+    type Output = L::Output;
+
+    fn mul(self, fv: ForceVector) -> L::Output {
+        self.left * (self.right * fv)
+    }
+}
+
+macro_rules! impl_compose_mul {
+    ($left:ty, $right:ty) => {
+        impl ops::Mul<$right> for $left {
+            type Output = Compose<$left, $right>;
+
+            fn mul(self, right: $right) -> Compose<$left, $right> {
+                compose(self, right)
+            }
+        }
+    };
+}
+
+impl_compose_mul!(Ad, Ad);
+impl_compose_mul!(AdDual, AdDual);
+impl_compose_mul!(AdDual, RBInertia);
+impl_compose_mul!(RBInertia, Ad);
+
+#[cfg(feature = "std")]
+// Coriolis matrix C such that C * alpha is the joint space bias force without
+// gravity, factorized as the sum over the bodies of
+//     J^T * (I * J_dot + (v x*) * I * J)
+// The column of J_dot associated with a joint j supporting the body i is
+// (v_j - v_i) x (X_j_i * s_j), velocities being expressed in the body i frame.
+#[allow(non_snake_case)]
+pub fn coriolis_matrix(mb: &MultiBody, q: &VecX, alpha: &VecX) -> MatX {
+    let dof = mb.nr_dof();
+    let poses = forward_kinematics(mb, q);
+    let velocities = forward_velocity(mb, q, alpha);
+    let mut coriolis = MatX::zeros(dof, dof);
+    for body in 0..mb.nr_bodies() {
+        let inertia = mb.bodies[body];
+        let v_i = velocities[body];
+        let momentum_rate = ad_dual(v_i) * inertia;
+        let mut jac = Mat6X::zeros(dof);
+        let mut jac_dot_term = Mat6X::zeros(dof);
+        for joint in mb.ancestors(body) {
+            let X_j_i = poses[body] * poses[joint].inv();
+            let relative = ad(X_j_i * velocities[joint] - v_i);
+            let s = mb.joints[joint].motion_subspace();
+            for k in 0..s.ncols() {
+                let column = X_j_i * MotionVector::from_vector(s.column(k).into_owned());
+                let col = mb.dof_offset(joint) + k;
+                jac.set_column(col, &column.vector());
+                let force = inertia * (relative * column) + momentum_rate * column;
+                jac_dot_term.set_column(col, &force.vector());
+            }
+        }
+        let contribution = jac.transpose() * jac_dot_term;
+        coriolis += MatX::from_fn(dof, dof, |r, c| contribution[(r, c)]);
+    }
+    coriolis
+}
@@ -0,0 +1,157 @@
+use std::convert::TryFrom;
+
+use sva::*;
+
+// Admittance controller integrating the virtual dynamics
+//     M * a + D * v + K * e = f
+// where e is the deflection of the compliant frame with respect to the
+// reference frame.
+// The measured force, velocity and acceleration are expressed in the
+// compliant frame and the offset is stored as X_ref_compliant.
+#[derive(Clone, Copy, Debug)]
+pub struct AdmittanceController {
+    inverse_mass: AdmittanceMatrix,
+    pub damping: ImpedanceMatrix,
+    pub stiffness: ImpedanceMatrix,
+    // component-wise saturations, expressed in the reference frame
+    // orientation for the deflection and in the compliant frame for the velocity
+    pub max_velocity: Option<MotionVector>,
+    pub max_deflection: Option<MotionVector>,
+    offset: PTransform,
+    velocity: MotionVector,
+}
+
+impl AdmittanceController {
+    // Returns None if the mass is not invertible
+    pub fn new(
+        mass: ImpedanceMatrix,
+        damping: ImpedanceMatrix,
+        stiffness: ImpedanceMatrix,
+    ) -> Option<Self> {
+        mass.inverse()
+            .map(|inverse_mass| Self::from_inverse_mass(inverse_mass, damping, stiffness))
+    }
+
+    // The inverse mass may be singular, a zero admittance locking the
+    // corresponding directions as an infinite mass would
+    pub fn from_inverse_mass(
+        inverse_mass: AdmittanceMatrix,
+        damping: ImpedanceMatrix,
+        stiffness: ImpedanceMatrix,
+    ) -> Self {
+        Self {
+            inverse_mass,
+            damping,
+            stiffness,
+            max_velocity: None,
+            max_deflection: None,
+            offset: PTransform::identity(),
+            velocity: MotionVector::zero(),
+        }
+    }
+
+    pub fn from_vectors(
+        mass: ImpedanceVector,
+        damping: ImpedanceVector,
+        stiffness: ImpedanceVector,
+    ) -> Option<Self> {
+        AdmittanceController::new(
+            ImpedanceMatrix::try_from(mass).ok()?,
+            ImpedanceMatrix::try_from(damping).ok()?,
+            ImpedanceMatrix::try_from(stiffness).ok()?,
+        )
+    }
+
+    // Returns None if a component is negative
+    pub fn from_inverse_mass_vectors(
+        inverse_mass: AdmittanceVector,
+        damping: ImpedanceVector,
+        stiffness: ImpedanceVector,
+    ) -> Option<Self> {
+        Some(AdmittanceController::from_inverse_mass(
+            AdmittanceMatrix::try_from(inverse_mass).ok()?,
+            ImpedanceMatrix::try_from(damping).ok()?,
+            ImpedanceMatrix::try_from(stiffness).ok()?,
+        ))
+    }
+
+    pub fn with_max_velocity(mut self, max_velocity: MotionVector) -> Self {
+        self.max_velocity = Some(max_velocity);
+        self
+    }
+
+    pub fn with_max_deflection(mut self, max_deflection: MotionVector) -> Self {
+        self.max_deflection = Some(max_deflection);
+        self
+    }
+
+    pub fn offset(&self) -> PTransform {
+        self.offset
+    }
+
+    pub fn velocity(&self) -> MotionVector {
+        self.velocity
+    }
+
+    // Compliant pose X_0_compliant given the reference pose X_0_ref
+    #[allow(non_snake_case)]
+    pub fn pose(&self, X_0_ref: &PTransform) -> PTransform {
+        &self.offset * X_0_ref
+    }
+
+    // Deflection of the compliant frame expressed in the compliant frame
+    pub fn deflection(&self) -> MotionVector {
+        PTransform::from_mat(self.offset.rotation()) * transform_velocity(&self.offset)
+    }
+
+    pub fn reset(&mut self) {
+        self.offset = PTransform::identity();
+        self.velocity = MotionVector::zero();
+    }
+
+    pub fn update(&mut self, force: &ForceVector, dt: f64) -> PTransform {
+        let spring_damper = self.damping * self.velocity + self.stiffness * self.deflection();
+        let acceleration = self.inverse_mass * (*force - spring_damper);
+
+        self.velocity += acceleration * dt;
+        if let Some(max_velocity) = self.max_velocity {
+            self.velocity = MotionVector::from_vector(saturate(
+                &self.velocity.vector(),
+                &max_velocity.vector(),
+            ));
+        }
+
+        self.offset = transform_exp(&(self.velocity * dt)) * self.offset;
+        if let Some(max_deflection) = self.max_deflection {
+            self.saturate_deflection(&max_deflection);
+        }
+
+        self.offset
+    }
+
+    #[allow(non_snake_case)]
+    fn saturate_deflection(&mut self, max_deflection: &MotionVector) {
+        let deflection = transform_velocity(&self.offset).vector();
+        let saturated = saturate(&deflection, &max_deflection.vector());
+        if saturated == deflection {
+            return;
+        }
+
+        let saturated = MotionVector::from_vector(saturated);
+        self.offset = PTransform::from_mat_vec(Rot3::new(-saturated.angular), saturated.linear);
+
+        // stop the motion along the saturated directions
+        let E_compliant_ref = PTransform::from_mat(self.offset.rotation().transpose());
+        let mut velocity = (E_compliant_ref * self.velocity).vector();
+        for i in 0..6 {
+            if saturated.vector()[i] != deflection[i] && velocity[i] * deflection[i] > 0. {
+                velocity[i] = 0.;
+            }
+        }
+        self.velocity = E_compliant_ref.inv() * MotionVector::from_vector(velocity);
+    }
+}
+
+fn saturate(vector: &Vec6, max: &Vec6) -> Vec6 {
+    vector.zip_map(max, |x, m| x.clamp(-m, m))
+}
@@ -0,0 +1,114 @@
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::fmt;
+use std::ops;
+
+use sva::*;
+
+// Symmetric positive semi-definite admittance, maps a ForceVector to a MotionVector.
+// from_matrix only accepts positive-definite matrices but diagonal conversions,
+// sums and products by non-negative scalars may be singular (e.g. a zero
+// stiffness), in which case inverse() returns None.
+#[derive(Clone, Copy, Debug)]
+pub struct AdmittanceMatrix {
+    matrix: Mat6,
+}
+
+impl AdmittanceMatrix {
+    pub fn identity() -> Self {
+        Self {
+            matrix: Mat6::identity(),
+        }
+    }
+
+    // Returns None if the matrix is not symmetric positive-definite
+    pub fn from_matrix(matrix: Mat6) -> Option<Self> {
+        if is_symmetric_positive_definite(&matrix) {
+            Some(Self { matrix })
+        } else {
+            None
+        }
+    }
+
+    // The matrix must be symmetric positive semi-definite
+    pub fn from_matrix_unchecked(matrix: Mat6) -> Self {
+        Self { matrix }
+    }
+
+    pub fn from_blocks(angular: Mat3, coupling: Mat3, linear: Mat3) -> Option<Self> {
+        AdmittanceMatrix::from_matrix(mat6_from_blocks(&angular, &coupling, &linear))
+    }
+
+    pub fn matrix(&self) -> Mat6 {
+        self.matrix
+    }
+
+    pub fn inverse(&self) -> Option<ImpedanceMatrix> {
+        self.matrix
+            .cholesky()
+            .map(|chol| ImpedanceMatrix::from_matrix_unchecked(chol.inverse()))
+    }
+}
+
+// Diagonal matrix, the components must be non-negative
+impl TryFrom<AdmittanceVector> for AdmittanceMatrix {
+    type Error = NegativeComponentError;
+
+    fn try_from(admittance_vector: AdmittanceVector) -> Result<Self, Self::Error> {
+        let diagonal = admittance_vector.vector();
+        if diagonal.iter().any(|&x| x < 0.) {
+            Err(NegativeComponentError)
+        } else {
+            let matrix = Mat6::from_diagonal(&diagonal);
+            Ok(AdmittanceMatrix::from_matrix_unchecked(matrix))
+        }
+    }
+}
+
+impl ops::Add<AdmittanceMatrix> for AdmittanceMatrix {
+    type Output = AdmittanceMatrix;
+
+    // the sum of positive semi-definite matrices remains so
+    fn add(self, other: AdmittanceMatrix) -> AdmittanceMatrix {
+        AdmittanceMatrix::from_matrix_unchecked(self.matrix + other.matrix)
+    }
+}
+
+impl ops::Mul<f64> for AdmittanceMatrix {
+    type Output = AdmittanceMatrix;
+
+    // Panics if the scalar is negative
+    fn mul(self, scalar: f64) -> AdmittanceMatrix {
+        assert!(scalar >= 0., "the scalar must be non-negative");
+        AdmittanceMatrix::from_matrix_unchecked(scalar * self.matrix)
+    }
+}
+
+impl ops::Mul<AdmittanceMatrix> for f64 {
+    type Output = AdmittanceMatrix;
+
+    fn mul(self, admittance_matrix: AdmittanceMatrix) -> AdmittanceMatrix {
+        admittance_matrix * self
+    }
+}
+
+impl ops::Mul<ForceVector> for AdmittanceMatrix {
+    type Output = MotionVector;
+
+    fn mul(self, force_vector: ForceVector) -> MotionVector {
+        MotionVector::from_vector(self.matrix * force_vector.vector())
+    }
+}
+
+impl std::cmp::PartialEq for AdmittanceMatrix {
+    fn eq(&self, other: &AdmittanceMatrix) -> bool {
+        self.matrix == other.matrix
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for AdmittanceMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.matrix)
+    }
+}
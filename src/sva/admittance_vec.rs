@@ -1,14 +1,24 @@
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
 use std::fmt;
 use std::ops;
 
 use sva::*;
 
+// repr(C) stores the six components contiguously, see AsRef<[f64]>
 #[derive(Clone, Copy, Debug)]
+#[repr(C)]
 pub struct AdmittanceVector {
     pub angular: Vec3,
     pub linear: Vec3,
 }
 
+impl Default for AdmittanceVector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AdmittanceVector {
     pub fn zero() -> Self {
         Self {
@@ -30,8 +40,8 @@ impl AdmittanceVector {
 
     pub fn from_vectors(angular: Vec3, linear: Vec3) -> Self {
         Self {
-            angular: angular,
-            linear: linear,
+            angular,
+            linear,
         }
     }
 
@@ -53,6 +63,20 @@ impl AdmittanceVector {
         )
     }
 
+    // Panics if the slice does not hold 6 elements
+    pub fn from_slice(slice: &[f64]) -> Self {
+        assert_eq!(slice.len(), 6);
+        Self {
+            angular: Vec3::new(slice[0], slice[1], slice[2]),
+            linear: Vec3::new(slice[3], slice[4], slice[5]),
+        }
+    }
+
+    // Panics if the slice does not hold 6 elements
+    pub fn copy_to_slice(&self, slice: &mut [f64]) {
+        slice.copy_from_slice(self.as_ref());
+    }
+
     pub fn cross(&self, other: AdmittanceVector) -> AdmittanceVector {
         AdmittanceVector::from_vectors(
             self.angular.cross(&other.angular),
@@ -70,6 +94,21 @@ impl AdmittanceVector {
     pub fn dot(&self, other: ForceVector) -> f64 {
         self.angular.dot(&other.couple) + self.linear.dot(&other.force)
     }
+
+    // Returns None if any of the components is zero
+    pub fn inverse(&self) -> Option<ImpedanceVector> {
+        let angular = component_inverse(&self.angular)?;
+        let linear = component_inverse(&self.linear)?;
+        Some(ImpedanceVector::from_vectors(angular, linear))
+    }
+}
+
+impl TryFrom<ImpedanceVector> for AdmittanceVector {
+    type Error = ZeroComponentError;
+
+    fn try_from(impedance_vector: ImpedanceVector) -> Result<Self, Self::Error> {
+        impedance_vector.inverse().ok_or(ZeroComponentError)
+    }
 }
 
 impl ops::Add<AdmittanceVector> for AdmittanceVector {
@@ -174,6 +213,39 @@ impl std::cmp::PartialEq for AdmittanceVector {
     }
 }
 
+impl From<Vec6> for AdmittanceVector {
+    fn from(vector: Vec6) -> Self {
+        AdmittanceVector::from_vector(vector)
+    }
+}
+
+impl From<AdmittanceVector> for Vec6 {
+    fn from(vector: AdmittanceVector) -> Self {
+        vector.vector()
+    }
+}
+
+impl From<[f64; 6]> for AdmittanceVector {
+    fn from(array: [f64; 6]) -> Self {
+        AdmittanceVector::from_slice(&array)
+    }
+}
+
+impl AsRef<[f64]> for AdmittanceVector {
+    fn as_ref(&self) -> &[f64] {
+        vec3_pair_as_slice(self)
+    }
+}
+
+impl ops::Index<usize> for AdmittanceVector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.as_ref()[index]
+    }
+}
+
+#[cfg(feature = "std")]
 impl fmt::Display for AdmittanceVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
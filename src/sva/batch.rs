@@ -0,0 +1,262 @@
+use sva::*;
+
+// Motion and force vectors stored as structure of arrays: each of the six
+// components lives in its own contiguous buffer so that transforming a batch
+// reduces to simple loops that the compiler can vectorize.
+// See benches/batch.rs for a comparison with the one vector at a time path.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MotionVectorBatch {
+    // angular x, y, z then linear x, y, z
+    components: [Vec<f64>; 6],
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ForceVectorBatch {
+    // couple x, y, z then force x, y, z
+    components: [Vec<f64>; 6],
+}
+
+macro_rules! impl_batch {
+    ($batch:ident, $vector:ident, $first:ident, $second:ident) => {
+        impl $batch {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self {
+                    components: [
+                        Vec::with_capacity(capacity),
+                        Vec::with_capacity(capacity),
+                        Vec::with_capacity(capacity),
+                        Vec::with_capacity(capacity),
+                        Vec::with_capacity(capacity),
+                        Vec::with_capacity(capacity),
+                    ],
+                }
+            }
+
+            pub fn from_vectors(vectors: &[$vector]) -> Self {
+                let mut batch = Self::with_capacity(vectors.len());
+                for vector in vectors {
+                    batch.push(vector);
+                }
+                batch
+            }
+
+            pub fn to_vectors(&self) -> Vec<$vector> {
+                (0..self.len()).map(|i| self.get(i)).collect()
+            }
+
+            pub fn len(&self) -> usize {
+                self.components[0].len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.len() == 0
+            }
+
+            pub fn clear(&mut self) {
+                for component in &mut self.components {
+                    component.clear();
+                }
+            }
+
+            pub fn push(&mut self, vector: &$vector) {
+                for (component, value) in self.components.iter_mut().zip(vector.as_ref()) {
+                    component.push(*value);
+                }
+            }
+
+            pub fn get(&self, index: usize) -> $vector {
+                let c = &self.components;
+                $vector::from_vectors(
+                    Vec3::new(c[0][index], c[1][index], c[2][index]),
+                    Vec3::new(c[3][index], c[4][index], c[5][index]),
+                )
+            }
+
+            pub fn set(&mut self, index: usize, vector: &$vector) {
+                for (component, value) in self.components.iter_mut().zip(vector.as_ref()) {
+                    component[index] = *value;
+                }
+            }
+
+            // Buffer of the given axis (0, 1 or 2) of the first component
+            pub fn $first(&self, axis: usize) -> &[f64] {
+                &self.components[axis]
+            }
+
+            // Buffer of the given axis (0, 1 or 2) of the second component
+            pub fn $second(&self, axis: usize) -> &[f64] {
+                &self.components[3 + axis]
+            }
+        }
+    };
+}
+
+impl_batch!(MotionVectorBatch, MotionVector, angular, linear);
+impl_batch!(ForceVectorBatch, ForceVector, couple, force);
+
+// Rotation and translation entries of a transformation, copied once per batch
+struct Coefficients {
+    e: [[f64; 3]; 3],
+    r: [f64; 3],
+}
+
+impl Coefficients {
+    fn new(transform: &PTransform) -> Self {
+        let (e, r) = (transform.rotation(), transform.translation());
+        Self {
+            e: [
+                [e[(0, 0)], e[(0, 1)], e[(0, 2)]],
+                [e[(1, 0)], e[(1, 1)], e[(1, 2)]],
+                [e[(2, 0)], e[(2, 1)], e[(2, 2)]],
+            ],
+            r: [r[0], r[1], r[2]],
+        }
+    }
+
+    #[inline(always)]
+    fn rotate(&self, x: [f64; 3]) -> [f64; 3] {
+        let e = &self.e;
+        [
+            e[0][0] * x[0] + e[0][1] * x[1] + e[0][2] * x[2],
+            e[1][0] * x[0] + e[1][1] * x[1] + e[1][2] * x[2],
+            e[2][0] * x[0] + e[2][1] * x[1] + e[2][2] * x[2],
+        ]
+    }
+
+    #[inline(always)]
+    fn rotate_transpose(&self, x: [f64; 3]) -> [f64; 3] {
+        let e = &self.e;
+        [
+            e[0][0] * x[0] + e[1][0] * x[1] + e[2][0] * x[2],
+            e[0][1] * x[0] + e[1][1] * x[1] + e[2][1] * x[2],
+            e[0][2] * x[0] + e[1][2] * x[1] + e[2][2] * x[2],
+        ]
+    }
+
+    // r x x
+    #[inline(always)]
+    fn cross(&self, x: [f64; 3]) -> [f64; 3] {
+        let r = &self.r;
+        [
+            r[1] * x[2] - r[2] * x[1],
+            r[2] * x[0] - r[0] * x[2],
+            r[0] * x[1] - r[1] * x[0],
+        ]
+    }
+
+    // (E * a, E * (b - r x a)), used by X * m and X* * f with swapped halves
+    #[inline(always)]
+    fn forward(&self, a: [f64; 3], b: [f64; 3]) -> ([f64; 3], [f64; 3]) {
+        let c = self.cross(a);
+        (
+            self.rotate(a),
+            self.rotate([b[0] - c[0], b[1] - c[1], b[2] - c[2]]),
+        )
+    }
+
+    // (E^T * a, E^T * b + r x (E^T * a)), used by X^-1 * m and X^T * f with
+    // swapped halves
+    #[inline(always)]
+    fn backward(&self, a: [f64; 3], b: [f64; 3]) -> ([f64; 3], [f64; 3]) {
+        let a = self.rotate_transpose(a);
+        let b = self.rotate_transpose(b);
+        let c = self.cross(a);
+        (a, [b[0] + c[0], b[1] + c[1], b[2] + c[2]])
+    }
+}
+
+// Apply f to the (a, b) halves of each element, the output being resized to
+// the input length
+#[inline(always)]
+fn map_components<F>(input: &[Vec<f64>; 6], output: &mut [Vec<f64>; 6], a: usize, b: usize, f: F)
+where
+    F: Fn([f64; 3], [f64; 3]) -> ([f64; 3], [f64; 3]),
+{
+    let n = input[0].len();
+    for component in output.iter_mut() {
+        component.resize(n, 0.);
+    }
+    let (ia, ib) = (&input[a..a + 3], &input[b..b + 3]);
+    let (ia0, ia1, ia2) = (&ia[0][..n], &ia[1][..n], &ia[2][..n]);
+    let (ib0, ib1, ib2) = (&ib[0][..n], &ib[1][..n], &ib[2][..n]);
+    let (head, tail) = output.split_at_mut(3);
+    let (oa, ob) = if a == 0 { (head, tail) } else { (tail, head) };
+    let (oa0, rest) = oa.split_at_mut(1);
+    let (oa1, oa2) = rest.split_at_mut(1);
+    let (ob0, rest) = ob.split_at_mut(1);
+    let (ob1, ob2) = rest.split_at_mut(1);
+    let (oa0, oa1, oa2) = (&mut oa0[0][..n], &mut oa1[0][..n], &mut oa2[0][..n]);
+    let (ob0, ob1, ob2) = (&mut ob0[0][..n], &mut ob1[0][..n], &mut ob2[0][..n]);
+    for i in 0..n {
+        let (ra, rb) = f([ia0[i], ia1[i], ia2[i]], [ib0[i], ib1[i], ib2[i]]);
+        oa0[i] = ra[0];
+        oa1[i] = ra[1];
+        oa2[i] = ra[2];
+        ob0[i] = rb[0];
+        ob1[i] = rb[1];
+        ob2[i] = rb[2];
+    }
+}
+
+impl PTransform {
+    // X * m for each motion vector of the batch
+    pub fn batch_mul(&self, batch: &MotionVectorBatch) -> MotionVectorBatch {
+        let mut output = MotionVectorBatch::new();
+        self.batch_mul_into(batch, &mut output);
+        output
+    }
+
+    pub fn batch_mul_into(&self, batch: &MotionVectorBatch, output: &mut MotionVectorBatch) {
+        let coeffs = Coefficients::new(self);
+        map_components(&batch.components, &mut output.components, 0, 3, |w, v| {
+            coeffs.forward(w, v)
+        });
+    }
+
+    // X^-1 * m for each motion vector of the batch
+    pub fn batch_inv_mul(&self, batch: &MotionVectorBatch) -> MotionVectorBatch {
+        let mut output = MotionVectorBatch::new();
+        self.batch_inv_mul_into(batch, &mut output);
+        output
+    }
+
+    pub fn batch_inv_mul_into(&self, batch: &MotionVectorBatch, output: &mut MotionVectorBatch) {
+        let coeffs = Coefficients::new(self);
+        map_components(&batch.components, &mut output.components, 0, 3, |w, v| {
+            coeffs.backward(w, v)
+        });
+    }
+
+    // X* * f for each force vector of the batch
+    pub fn batch_dual_mul(&self, batch: &ForceVectorBatch) -> ForceVectorBatch {
+        let mut output = ForceVectorBatch::new();
+        self.batch_dual_mul_into(batch, &mut output);
+        output
+    }
+
+    pub fn batch_dual_mul_into(&self, batch: &ForceVectorBatch, output: &mut ForceVectorBatch) {
+        let coeffs = Coefficients::new(self);
+        map_components(&batch.components, &mut output.components, 3, 0, |f, n| {
+            coeffs.forward(f, n)
+        });
+    }
+
+    // X^T * f for each force vector of the batch
+    pub fn batch_trans_mul(&self, batch: &ForceVectorBatch) -> ForceVectorBatch {
+        let mut output = ForceVectorBatch::new();
+        self.batch_trans_mul_into(batch, &mut output);
+        output
+    }
+
+    pub fn batch_trans_mul_into(&self, batch: &ForceVectorBatch, output: &mut ForceVectorBatch) {
+        let coeffs = Coefficients::new(self);
+        map_components(&batch.components, &mut output.components, 3, 0, |f, n| {
+            coeffs.backward(f, n)
+        });
+    }
+}
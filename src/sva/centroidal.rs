@@ -0,0 +1,105 @@
+use sva::*;
+
+// Centroidal quantities of a set of bodies.
+// All the slices are indexed by body: inertias are expressed in the body
+// frames, poses are X_0_i, velocities and bias accelerations (J_dot * q_dot)
+// are body quantities expressed in the body frames and Jacobians map the
+// joint velocities to the body velocities.
+// The centroidal frame G has its origin at the center of mass and the
+// orientation of the world frame.
+
+pub fn total_mass(inertias: &[RBInertia]) -> f64 {
+    inertias.iter().map(|inertia| inertia.mass).sum()
+}
+
+// Center of mass expressed in the world frame
+#[allow(non_snake_case)]
+pub fn center_of_mass(inertias: &[RBInertia], poses: &[PTransform]) -> Vec3 {
+    assert_eq!(inertias.len(), poses.len());
+    let weighted_sum = inertias
+        .iter()
+        .zip(poses)
+        .fold(Vec3::zeros(), |sum, (inertia, X_0_i)| {
+            sum + inertia.mass * X_0_i.translation()
+                + X_0_i.rotation().transpose() * inertia.momentum
+        });
+    weighted_sum / total_mass(inertias)
+}
+
+#[cfg(feature = "std")]
+// Jacobian of the center of mass expressed in the world frame
+#[allow(non_snake_case)]
+pub fn com_jacobian(inertias: &[RBInertia], poses: &[PTransform], jacobians: &[Mat6X]) -> Mat3X {
+    assert_eq!(inertias.len(), poses.len());
+    assert_eq!(inertias.len(), jacobians.len());
+    let dof = jacobians.first().map_or(0, |jac| jac.ncols());
+    let mut com_jac = Mat3X::zeros(dof);
+    for ((inertia, X_0_i), jac) in inertias.iter().zip(poses).zip(jacobians) {
+        // mass * velocity of the body com: m * v + w x h = m * v - h x w
+        let h_cross = vector3_to_cross_matrix(&inertia.momentum);
+        let point_jac = inertia.mass * jac.fixed_rows::<U3>(3) - h_cross * jac.fixed_rows::<U3>(0);
+        com_jac += X_0_i.rotation().transpose() * point_jac;
+    }
+    com_jac / total_mass(inertias)
+}
+
+// Total spatial momentum expressed in the centroidal frame
+#[allow(non_snake_case)]
+pub fn centroidal_momentum(
+    inertias: &[RBInertia],
+    poses: &[PTransform],
+    velocities: &[MotionVector],
+) -> ForceVector {
+    assert_eq!(inertias.len(), velocities.len());
+    let X_0_G = PTransform::from_vec(center_of_mass(inertias, poses));
+    inertias.iter().zip(poses).zip(velocities).fold(
+        ForceVector::zero(),
+        |sum, ((inertia, X_0_i), velocity)| {
+            sum + (*X_0_i * X_0_G.inv()).trans_mul(&(*inertia * *velocity))
+        },
+    )
+}
+
+#[cfg(feature = "std")]
+// Centroidal momentum matrix A such that h_G = A * q_dot
+#[allow(non_snake_case)]
+pub fn centroidal_momentum_matrix(
+    inertias: &[RBInertia],
+    poses: &[PTransform],
+    jacobians: &[Mat6X],
+) -> Mat6X {
+    assert_eq!(inertias.len(), jacobians.len());
+    let X_0_G = PTransform::from_vec(center_of_mass(inertias, poses));
+    let dof = jacobians.first().map_or(0, |jac| jac.ncols());
+    let mut cmm = Mat6X::zeros(dof);
+    for ((inertia, X_0_i), jac) in inertias.iter().zip(poses).zip(jacobians) {
+        let X_G_i = *X_0_i * X_0_G.inv();
+        cmm += X_G_i.matrix().transpose() * (inertia.matrix() * jac);
+    }
+    cmm
+}
+
+// Bias term A_dot * q_dot of the centroidal momentum derivative:
+// h_G_dot = A * q_ddot + A_dot * q_dot
+#[allow(non_snake_case)]
+pub fn centroidal_momentum_bias(
+    inertias: &[RBInertia],
+    poses: &[PTransform],
+    velocities: &[MotionVector],
+    bias_accelerations: &[MotionVector],
+) -> ForceVector {
+    assert_eq!(inertias.len(), velocities.len());
+    assert_eq!(inertias.len(), bias_accelerations.len());
+    let X_0_G = PTransform::from_vec(center_of_mass(inertias, poses));
+    let mut bias = ForceVector::zero();
+    for (((inertia, X_0_i), velocity), bias_acc) in inertias
+        .iter()
+        .zip(poses)
+        .zip(velocities)
+        .zip(bias_accelerations)
+    {
+        let body_bias = *inertia * *bias_acc + velocity.cross_dual(*inertia * *velocity);
+        bias += (*X_0_i * X_0_G.inv()).trans_mul(&body_bias);
+    }
+    bias
+}
@@ -0,0 +1,131 @@
+use std::f64::consts::PI;
+
+use sva::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactPoint {
+    pub position: Vec3,
+    // unit normal pointing towards the body receiving the contact force
+    pub normal: Vec3,
+    pub friction: f64,
+}
+
+impl ContactPoint {
+    pub fn new(position: Vec3, normal: Vec3, friction: f64) -> Self {
+        Self {
+            position,
+            normal: normal.normalize(),
+            friction,
+        }
+    }
+
+    // Generators of the friction pyramid inscribed in the friction cone
+    pub fn friction_pyramid(&self, nr_generators: usize) -> Vec<Vec3> {
+        linearized_friction_cone(&self.normal, self.friction, nr_generators)
+    }
+}
+
+// Edges of a pyramid with nr_generators faces inscribed in the friction cone
+// of the given normal and friction coefficient
+pub fn linearized_friction_cone(normal: &Vec3, friction: f64, nr_generators: usize) -> Vec<Vec3> {
+    let normal = normal.normalize();
+    let (t1, t2) = tangent_basis(&normal);
+    (0..nr_generators)
+        .map(|k| {
+            let theta = 2. * PI * k as f64 / nr_generators as f64;
+            (normal + friction * (theta.cos() * t1 + theta.sin() * t2)).normalize()
+        })
+        .collect()
+}
+
+// Polyhedral cone of the wrenches that a set of contact points can apply,
+// described by its generators
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactWrenchCone {
+    generators: Vec<ForceVector>,
+}
+
+impl ContactWrenchCone {
+    // Contact points are expressed in frame a and the cone in frame b
+    #[allow(non_snake_case)]
+    pub fn new(points: &[ContactPoint], nr_generators: usize, X_a_b: &PTransform) -> Self {
+        let generators = points
+            .iter()
+            .flat_map(|point| {
+                point
+                    .friction_pyramid(nr_generators)
+                    .into_iter()
+                    .map(move |force| {
+                        let wrench_a =
+                            ForceVector::from_vectors(point.position.cross(&force), force);
+                        X_a_b.dual_mul(&wrench_a)
+                    })
+            })
+            .collect();
+        Self { generators }
+    }
+
+    // Flat contact surface described by its vertices, all sharing the same normal
+    #[allow(non_snake_case)]
+    pub fn from_polygon(
+        vertices: &[Vec3],
+        normal: &Vec3,
+        friction: f64,
+        nr_generators: usize,
+        X_a_b: &PTransform,
+    ) -> Self {
+        let points: Vec<ContactPoint> = vertices
+            .iter()
+            .map(|vertex| ContactPoint::new(*vertex, *normal, friction))
+            .collect();
+        ContactWrenchCone::new(&points, nr_generators, X_a_b)
+    }
+
+    pub fn generators(&self) -> &[ForceVector] {
+        &self.generators
+    }
+
+    pub fn generators_matrix(&self) -> Mat6X {
+        let mut mat = Mat6X::zeros(self.generators.len());
+        for (i, generator) in self.generators.iter().enumerate() {
+            mat.set_column(i, &generator.vector());
+        }
+        mat
+    }
+
+    // Express the cone in frame c, given X_b_c
+    #[allow(non_snake_case)]
+    pub fn transform(&self, X_b_c: &PTransform) -> Self {
+        Self {
+            generators: self.generators.iter().map(|g| X_b_c.dual_mul(g)).collect(),
+        }
+    }
+
+    // Non-negative weights of the generators producing the wrench, if any
+    pub fn decompose(&self, wrench: &ForceVector) -> Option<VecX> {
+        let generators = self.generators_matrix();
+        let a = MatX::from_fn(6, generators.ncols(), |r, c| generators[(r, c)]);
+        let b = VecX::from_fn(6, |r, _| wrench.vector()[r]);
+        let weights = nnls(&a, &b);
+        let residual = (a * &weights - b).norm();
+        if residual <= 1e-8 * wrench.vector().norm().max(1.) {
+            Some(weights)
+        } else {
+            None
+        }
+    }
+
+    pub fn contains(&self, wrench: &ForceVector) -> bool {
+        self.decompose(wrench).is_some()
+    }
+}
+
+fn tangent_basis(normal: &Vec3) -> (Vec3, Vec3) {
+    let other = if normal[0].abs() < 0.9 {
+        Vec3::x()
+    } else {
+        Vec3::y()
+    };
+    let t1 = normal.cross(&other).normalize();
+    (t1, normal.cross(&t1))
+}
@@ -0,0 +1,206 @@
+#[cfg(feature = "std")]
+use std::fmt;
+use std::ops;
+
+use sva::*;
+
+// Below this norm of the rotation vector part, a dual quaternion is
+// considered as a pure translation
+const DUAL_QUAT_EPSILON: f64 = 1e-12;
+
+// Unit dual quaternion real + eps * dual representing the transformation
+// X_a_b: the point of coordinates p in a has coordinates E * p + t in b, with
+// real the rotation E and dual = 1/2 * t * real, where t = -E * r.
+// As with PTransform, X_a_c = X_b_c * X_a_b.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DualQuat {
+    pub real: Quaternion<f64>,
+    pub dual: Quaternion<f64>,
+}
+
+impl DualQuat {
+    pub fn new(real: Quaternion<f64>, dual: Quaternion<f64>) -> Self {
+        Self { real, dual }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(Quaternion::identity(), Quaternion::new(0., 0., 0., 0.))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn from_ptransform(X_a_b: &PTransform) -> Self {
+        let real = *Quat::from_rotation_matrix(&X_a_b.rotation()).quaternion();
+        let t = -(X_a_b.rotation() * X_a_b.translation());
+        Self::new(real, Quaternion::from_parts(0., t) * real * 0.5)
+    }
+
+    pub fn to_ptransform(&self) -> PTransform {
+        let rotation = self.rotation();
+        let translation = -(rotation.inverse() * self.point_translation());
+        PTransform::from_quat_vec(rotation, translation)
+    }
+
+    pub fn rotation(&self) -> Quat {
+        Quat::from_quaternion(self.real)
+    }
+
+    // Same as PTransform::translation: origin of b expressed in a
+    pub fn translation(&self) -> Vec3 {
+        self.to_ptransform().translation()
+    }
+
+    // t = 2 * dual * real^*
+    fn point_translation(&self) -> Vec3 {
+        (self.dual * self.real.conjugate() * 2.)
+            .vector()
+            .into_owned()
+    }
+
+    // Conjugate of both parts, which is the inverse of a unit dual quaternion
+    pub fn inv(&self) -> Self {
+        Self::new(self.real.conjugate(), self.dual.conjugate())
+    }
+
+    // Unit dual quaternion closest to this one: the real part is normalized
+    // and the dual part is made orthogonal to it
+    pub fn normalize(&self) -> Self {
+        let scale = 1. / self.real.norm();
+        let real = self.real * scale;
+        let dual = self.dual * scale;
+        Self::new(real, dual - real * real.dot(&dual))
+    }
+
+    // Coordinates in b of the point of coordinates p in a
+    pub fn transform_point(&self, point: &Vec3) -> Vec3 {
+        self.rotation() * point + self.point_translation()
+    }
+
+    pub fn inv_mul(&self, mv: &MotionVector) -> MotionVector {
+        self.to_ptransform().inv_mul(mv)
+    }
+
+    pub fn dual_mul(&self, fv: &ForceVector) -> ForceVector {
+        self.to_ptransform().dual_mul(fv)
+    }
+
+    pub fn trans_mul(&self, fv: &ForceVector) -> ForceVector {
+        self.to_ptransform().trans_mul(fv)
+    }
+
+    // Screw motion scaled by t, following the shortest path: the rotation
+    // angle and the translation along the screw axis are multiplied by t
+    pub fn pow(&self, t: f64) -> Self {
+        let dq = if self.real.w < 0. { -*self } else { *self };
+        let (w_r, v_r) = (dq.real.w, dq.real.vector().into_owned());
+        let (w_d, v_d) = (dq.dual.w, dq.dual.vector().into_owned());
+        let sin_half = v_r.norm();
+        if sin_half < DUAL_QUAT_EPSILON {
+            return Self::new(Quaternion::identity(), dq.dual * t);
+        }
+
+        // real = (cos(h), sin(h) * l) and
+        // dual = (-d/2 * sin(h), sin(h) * m + d/2 * cos(h) * l)
+        // for a rotation of 2 * h about the line (l, m) and a translation d
+        // along it
+        let half = libm::atan2(sin_half, w_r);
+        let axis = v_r / sin_half;
+        let pitch_translation = -2. * w_d / sin_half;
+        let moment = (v_d - axis * (pitch_translation * 0.5 * w_r)) / sin_half;
+
+        let (half, translation) = (half * t, pitch_translation * t);
+        let (sin, cos) = (libm::sin(half), libm::cos(half));
+        Self::new(
+            Quaternion::from_parts(cos, axis * sin),
+            Quaternion::from_parts(
+                -translation * 0.5 * sin,
+                moment * sin + axis * (translation * 0.5 * cos),
+            ),
+        )
+    }
+}
+
+impl From<PTransform> for DualQuat {
+    fn from(transform: PTransform) -> Self {
+        DualQuat::from_ptransform(&transform)
+    }
+}
+
+impl From<DualQuat> for PTransform {
+    fn from(dual_quat: DualQuat) -> Self {
+        dual_quat.to_ptransform()
+    }
+}
+
+impl ops::Mul<DualQuat> for DualQuat {
+    type Output = DualQuat;
+
+    fn mul(self, other: DualQuat) -> DualQuat {
+        DualQuat::new(
+            self.real * other.real,
+            self.real * other.dual + self.dual * other.real,
+        )
+    }
+}
+
+impl ops::Mul<MotionVector> for DualQuat {
+    type Output = MotionVector;
+
+    fn mul(self, mv: MotionVector) -> MotionVector {
+        self.to_ptransform() * mv
+    }
+}
+
+impl ops::Neg for DualQuat {
+    type Output = DualQuat;
+
+    fn neg(self) -> DualQuat {
+        DualQuat::new(-self.real, -self.dual)
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DualQuat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "(real: [{rw} {rx} {ry} {rz}], dual: [{dw} {dx} {dy} {dz}])",
+            rw = self.real.w,
+            rx = self.real.i,
+            ry = self.real.j,
+            rz = self.real.k,
+            dw = self.dual.w,
+            dx = self.dual.i,
+            dy = self.dual.j,
+            dz = self.dual.k
+        )
+    }
+}
+
+// Screw linear interpolation between transformations, t being between 0 and 1
+pub fn sclerp(from: &DualQuat, to: &DualQuat, t: f64) -> DualQuat {
+    (*to * from.inv()).pow(t) * *from
+}
+
+// Dual quaternion linear blending: normalized weighted sum of the dual
+// quaternions, each one being taken in the hemisphere of the first one.
+// Returns None if there are no dual quaternions or if the sum vanishes.
+pub fn dual_quat_blend(dual_quats: &[DualQuat], weights: &[f64]) -> Option<DualQuat> {
+    assert_eq!(dual_quats.len(), weights.len());
+    let first = dual_quats.first()?;
+    let zero = Quaternion::new(0., 0., 0., 0.);
+    let mut sum = DualQuat::new(zero, zero);
+    for (dq, &weight) in dual_quats.iter().zip(weights) {
+        let weight = if dq.real.dot(&first.real) < 0. {
+            -weight
+        } else {
+            weight
+        };
+        sum.real += dq.real * weight;
+        sum.dual += dq.dual * weight;
+    }
+    if sum.real.norm() < DUAL_QUAT_EPSILON {
+        None
+    } else {
+        Some(sum.normalize())
+    }
+}
@@ -0,0 +1,47 @@
+use sva::*;
+
+// Energies and powers of a set of bodies.
+// As in centroidal, the slices are indexed by body with inertias and
+// velocities expressed in the body frames and poses X_0_i.
+
+// Sum of 1/2 * v^T * I * v
+pub fn kinetic_energy(inertias: &[RBInertia], velocities: &[MotionVector]) -> f64 {
+    assert_eq!(inertias.len(), velocities.len());
+    inertias
+        .iter()
+        .zip(velocities)
+        .map(|(inertia, velocity)| 0.5 * velocity.dot(*inertia * *velocity))
+        .sum()
+}
+
+// Gravitational potential energy, zero at the world origin
+pub fn potential_energy(inertias: &[RBInertia], poses: &[PTransform], gravity: &Vec3) -> f64 {
+    -total_mass(inertias) * gravity.dot(&center_of_mass(inertias, poses))
+}
+
+// Instantaneous power of forces applied to bodies moving with the given
+// velocities, both expressed in the same frames
+pub fn power(forces: &[ForceVector], velocities: &[MotionVector]) -> f64 {
+    assert_eq!(forces.len(), velocities.len());
+    forces
+        .iter()
+        .zip(velocities)
+        .map(|(force, velocity)| velocity.dot(*force))
+        .sum()
+}
+
+#[cfg(feature = "std")]
+pub fn tree_kinetic_energy(mb: &MultiBody, q: &VecX, alpha: &VecX) -> f64 {
+    kinetic_energy(&mb.bodies, &forward_velocity(mb, q, alpha))
+}
+
+#[cfg(feature = "std")]
+pub fn tree_potential_energy(mb: &MultiBody, q: &VecX, gravity: &Vec3) -> f64 {
+    potential_energy(&mb.bodies, &forward_kinematics(mb, q), gravity)
+}
+
+#[cfg(feature = "std")]
+// Sum of the kinetic and potential energies
+pub fn tree_energy(mb: &MultiBody, q: &VecX, alpha: &VecX, gravity: &Vec3) -> f64 {
+    tree_kinetic_energy(mb, q, alpha) + tree_potential_energy(mb, q, gravity)
+}
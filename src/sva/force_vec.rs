@@ -1,14 +1,23 @@
+#[cfg(feature = "std")]
 use std::fmt;
 use std::ops;
 
 use sva::*;
 
+// repr(C) stores the six components contiguously, see AsRef<[f64]>
 #[derive(Clone, Copy, Debug)]
+#[repr(C)]
 pub struct ForceVector {
     pub couple: Vec3,
     pub force: Vec3,
 }
 
+impl Default for ForceVector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ForceVector {
     pub fn zero() -> Self {
         Self {
@@ -30,8 +39,8 @@ impl ForceVector {
 
     pub fn from_vectors(couple: Vec3, force: Vec3) -> Self {
         Self {
-            couple: couple,
-            force: force,
+            couple,
+            force,
         }
     }
 
@@ -45,6 +54,39 @@ impl ForceVector {
             self.force[2],
         )
     }
+
+    // Panics if the slice does not hold 6 elements
+    pub fn from_slice(slice: &[f64]) -> Self {
+        assert_eq!(slice.len(), 6);
+        Self {
+            couple: Vec3::new(slice[0], slice[1], slice[2]),
+            force: Vec3::new(slice[3], slice[4], slice[5]),
+        }
+    }
+
+    // Panics if the slice does not hold 6 elements
+    pub fn copy_to_slice(&self, slice: &mut [f64]) {
+        slice.copy_from_slice(self.as_ref());
+    }
+
+    // Cross product from the force side: f x m = -(m x* f)
+    pub fn cross(&self, other: MotionVector) -> ForceVector {
+        -other.cross_dual(*self)
+    }
+
+    pub fn dot(&self, other: MotionVector) -> f64 {
+        other.dot(*self)
+    }
+
+    // sqrt(f . (W * f)), the weights being non-negative
+    pub fn weighted_norm(&self, weights: &AdmittanceVector) -> f64 {
+        libm::sqrt(self.dot(*weights * *self))
+    }
+
+    // sqrt(f . (A * f)) for a symmetric positive-definite admittance A
+    pub fn metric_norm(&self, metric: &AdmittanceMatrix) -> f64 {
+        libm::sqrt(self.dot(*metric * *self))
+    }
 }
 
 impl ops::Add<ForceVector> for ForceVector {
@@ -83,6 +125,15 @@ impl ops::Neg for ForceVector {
     }
 }
 
+// Natural pairing with motion vectors
+impl ops::Mul<MotionVector> for ForceVector {
+    type Output = f64;
+
+    fn mul(self, mv: MotionVector) -> f64 {
+        self.dot(mv)
+    }
+}
+
 impl ops::Mul<f64> for ForceVector {
     type Output = ForceVector;
 
@@ -99,6 +150,16 @@ impl ops::Mul<ForceVector> for f64 {
     }
 }
 
+// Product by a matrix mapping ForceVector to ForceVector (transformations, cross
+// product matrices, ...), other matrices must go through vector()
+impl ops::Mul<ForceVector> for Mat6 {
+    type Output = ForceVector;
+
+    fn mul(self, vector: ForceVector) -> ForceVector {
+        ForceVector::from_vector(self * vector.vector())
+    }
+}
+
 impl ops::MulAssign<f64> for ForceVector {
     fn mul_assign(&mut self, scalar: f64) {
         *self = ForceVector::from_vectors(scalar * self.couple, scalar * self.force)
@@ -125,6 +186,39 @@ impl std::cmp::PartialEq for ForceVector {
     }
 }
 
+impl From<Vec6> for ForceVector {
+    fn from(vector: Vec6) -> Self {
+        ForceVector::from_vector(vector)
+    }
+}
+
+impl From<ForceVector> for Vec6 {
+    fn from(vector: ForceVector) -> Self {
+        vector.vector()
+    }
+}
+
+impl From<[f64; 6]> for ForceVector {
+    fn from(array: [f64; 6]) -> Self {
+        ForceVector::from_slice(&array)
+    }
+}
+
+impl AsRef<[f64]> for ForceVector {
+    fn as_ref(&self) -> &[f64] {
+        vec3_pair_as_slice(self)
+    }
+}
+
+impl ops::Index<usize> for ForceVector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.as_ref()[index]
+    }
+}
+
+#[cfg(feature = "std")]
 impl fmt::Display for ForceVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+
+use sva::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FrameTreeError {
+    UnknownFrame(String),
+    DuplicateFrame(String),
+    // setting the parent would create a cycle
+    Cycle { frame: String, parent: String },
+    Disconnected { from: String, to: String },
+    // the edge between the frame and its parent has no sample at this time
+    OutOfRange { frame: String, time: f64 },
+}
+
+impl fmt::Display for FrameTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrameTreeError::UnknownFrame(frame) => write!(f, "unknown frame '{}'", frame),
+            FrameTreeError::DuplicateFrame(frame) => {
+                write!(f, "frame '{}' already exists", frame)
+            }
+            FrameTreeError::Cycle { frame, parent } => write!(
+                f,
+                "setting '{}' as the parent of '{}' would create a cycle",
+                parent, frame
+            ),
+            FrameTreeError::Disconnected { from, to } => {
+                write!(f, "frames '{}' and '{}' are not connected", from, to)
+            }
+            FrameTreeError::OutOfRange { frame, time } => write!(
+                f,
+                "no transform available for frame '{}' at time {}",
+                frame, time
+            ),
+        }
+    }
+}
+
+impl error::Error for FrameTreeError {}
+
+// Time-stamped transformations X_parent_frame of a single edge, sorted by time
+#[derive(Clone, Debug, Default)]
+pub struct TransformBuffer {
+    samples: Vec<(f64, PTransform)>,
+    is_static: bool,
+}
+
+impl TransformBuffer {
+    pub fn new() -> Self {
+        Self {
+            samples: Vec::new(),
+            is_static: false,
+        }
+    }
+
+    pub fn insert(&mut self, time: f64, transform: PTransform) {
+        if self.is_static {
+            self.samples.clear();
+            self.is_static = false;
+        }
+        let index = self.samples.iter().position(|&(t, _)| t >= time);
+        match index {
+            Some(i) if self.samples[i].0 == time => self.samples[i].1 = transform,
+            Some(i) => self.samples.insert(i, (time, transform)),
+            None => self.samples.push((time, transform)),
+        }
+    }
+
+    pub fn set_static(&mut self, transform: PTransform) {
+        self.samples.clear();
+        self.samples.push((0., transform));
+        self.is_static = true;
+    }
+
+    // Remove the samples older than the given time, keeping the one just
+    // before it so that lookups at this time are still possible
+    pub fn prune_before(&mut self, time: f64) {
+        if self.is_static {
+            return;
+        }
+        let first_kept = self.samples.iter().rposition(|&(t, _)| t <= time);
+        if let Some(first_kept) = first_kept {
+            self.samples.drain(..first_kept);
+        }
+    }
+
+    pub fn time_range(&self) -> Option<(f64, f64)> {
+        match (self.samples.first(), self.samples.last()) {
+            (Some(first), Some(last)) if !self.is_static => Some((first.0, last.0)),
+            _ => None,
+        }
+    }
+
+    pub fn lookup(&self, time: f64) -> Option<PTransform> {
+        if self.is_static {
+            return self.samples.first().map(|&(_, transform)| transform);
+        }
+        let after = self.samples.iter().position(|&(t, _)| t >= time)?;
+        let (t1, to) = self.samples[after];
+        if t1 == time {
+            return Some(to);
+        }
+        if after == 0 {
+            return None;
+        }
+        let (t0, from) = self.samples[after - 1];
+        Some(interpolate(&from, &to, (time - t0) / (t1 - t0)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct FrameNode {
+    parent: Option<String>,
+    // X_parent_frame
+    buffer: TransformBuffer,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FrameTree {
+    frames: HashMap<String, FrameNode>,
+    buffer_duration: Option<f64>,
+}
+
+impl FrameTree {
+    pub fn new() -> Self {
+        Self {
+            frames: HashMap::new(),
+            buffer_duration: None,
+        }
+    }
+
+    // Samples older than the latest one minus this duration get discarded
+    pub fn with_buffer_duration(mut self, duration: f64) -> Self {
+        self.buffer_duration = Some(duration);
+        self
+    }
+
+    pub fn has_frame(&self, name: &str) -> bool {
+        self.frames.contains_key(name)
+    }
+
+    pub fn parent(&self, name: &str) -> Result<Option<&str>, FrameTreeError> {
+        Ok(self.node(name)?.parent.as_deref())
+    }
+
+    pub fn add_root(&mut self, name: &str) -> Result<(), FrameTreeError> {
+        self.insert_node(name, None)
+    }
+
+    pub fn add_frame(&mut self, name: &str, parent: &str) -> Result<(), FrameTreeError> {
+        self.node(parent)?;
+        self.insert_node(name, Some(parent.to_string()))
+    }
+
+    // Attach a frame to a new parent, its previous transformations are dropped
+    pub fn set_parent(&mut self, name: &str, parent: &str) -> Result<(), FrameTreeError> {
+        self.node(name)?;
+        self.node(parent)?;
+        if self.ancestors(parent)?.contains(&name) {
+            return Err(FrameTreeError::Cycle {
+                frame: name.to_string(),
+                parent: parent.to_string(),
+            });
+        }
+        let node = self.frames.get_mut(name).unwrap();
+        node.parent = Some(parent.to_string());
+        node.buffer = TransformBuffer::new();
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    pub fn set_transform(
+        &mut self,
+        name: &str,
+        time: f64,
+        X_parent_frame: PTransform,
+    ) -> Result<(), FrameTreeError> {
+        let buffer_duration = self.buffer_duration;
+        let buffer = &mut self.node_mut(name)?.buffer;
+        buffer.insert(time, X_parent_frame);
+        if let (Some(duration), Some((_, latest))) = (buffer_duration, buffer.time_range()) {
+            buffer.prune_before(latest - duration);
+        }
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    pub fn set_static_transform(
+        &mut self,
+        name: &str,
+        X_parent_frame: PTransform,
+    ) -> Result<(), FrameTreeError> {
+        self.node_mut(name)?.buffer.set_static(X_parent_frame);
+        Ok(())
+    }
+
+    // Transformation X_from_to at the given time
+    #[allow(non_snake_case)]
+    pub fn transform(&self, from: &str, to: &str, time: f64) -> Result<PTransform, FrameTreeError> {
+        let from_ancestors = self.ancestors(from)?;
+        let to_ancestors = self.ancestors(to)?;
+        let common = from_ancestors
+            .iter()
+            .position(|frame| to_ancestors.contains(frame))
+            .ok_or_else(|| FrameTreeError::Disconnected {
+                from: from.to_string(),
+                to: to.to_string(),
+            })?;
+        let common_frame = from_ancestors[common];
+
+        let X_common_from = self.transform_to_ancestor(&from_ancestors[..common], time)?;
+        let to_common = to_ancestors
+            .iter()
+            .position(|&f| f == common_frame)
+            .unwrap();
+        let X_common_to = self.transform_to_ancestor(&to_ancestors[..to_common], time)?;
+        Ok(X_common_to * X_common_from.inv())
+    }
+
+    pub fn transform_motion(
+        &self,
+        from: &str,
+        to: &str,
+        time: f64,
+        mv: &MotionVector,
+    ) -> Result<MotionVector, FrameTreeError> {
+        Ok(self.transform(from, to, time)? * *mv)
+    }
+
+    pub fn transform_force(
+        &self,
+        from: &str,
+        to: &str,
+        time: f64,
+        fv: &ForceVector,
+    ) -> Result<ForceVector, FrameTreeError> {
+        Ok(self.transform(from, to, time)?.dual_mul(fv))
+    }
+
+    // X_ancestor_frame where path goes from the frame up to (excluding) the ancestor
+    #[allow(non_snake_case)]
+    fn transform_to_ancestor(
+        &self,
+        path: &[&str],
+        time: f64,
+    ) -> Result<PTransform, FrameTreeError> {
+        let mut X_ancestor_frame = PTransform::identity();
+        for &frame in path {
+            let X_parent_frame = self.node(frame)?.buffer.lookup(time).ok_or_else(|| {
+                FrameTreeError::OutOfRange {
+                    frame: frame.to_string(),
+                    time,
+                }
+            })?;
+            X_ancestor_frame = X_ancestor_frame * X_parent_frame;
+        }
+        Ok(X_ancestor_frame)
+    }
+
+    // The frame itself followed by its parents up to its root
+    fn ancestors<'a>(&'a self, name: &'a str) -> Result<Vec<&'a str>, FrameTreeError> {
+        let mut ancestors = vec![name];
+        let mut node = self.node(name)?;
+        while let Some(ref parent) = node.parent {
+            ancestors.push(parent.as_str());
+            node = self.node(parent)?;
+        }
+        Ok(ancestors)
+    }
+
+    fn insert_node(&mut self, name: &str, parent: Option<String>) -> Result<(), FrameTreeError> {
+        if self.has_frame(name) {
+            return Err(FrameTreeError::DuplicateFrame(name.to_string()));
+        }
+        self.frames.insert(
+            name.to_string(),
+            FrameNode {
+                parent,
+                buffer: TransformBuffer::new(),
+            },
+        );
+        Ok(())
+    }
+
+    fn node(&self, name: &str) -> Result<&FrameNode, FrameTreeError> {
+        self.frames
+            .get(name)
+            .ok_or_else(|| FrameTreeError::UnknownFrame(name.to_string()))
+    }
+
+    fn node_mut(&mut self, name: &str) -> Result<&mut FrameNode, FrameTreeError> {
+        self.frames
+            .get_mut(name)
+            .ok_or_else(|| FrameTreeError::UnknownFrame(name.to_string()))
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt;
+
+use sva::*;
+
+// Drive the pose X_0_body of a body toward the target X_0_target.
+// Errors and Jacobians are expressed at the body origin with the world frame
+// orientation, as returned by transform_error. Each error component is scaled
+// by the weight (a zero weight frees the axis) and tasks with a lower priority
+// value are solved first, the next ones acting in their null space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IkTask {
+    pub body: usize,
+    pub target: PTransform,
+    pub weight: ImpedanceVector,
+    pub priority: usize,
+}
+
+impl IkTask {
+    pub fn new(body: usize, target: PTransform) -> Self {
+        Self {
+            body,
+            target,
+            weight: ImpedanceVector::from_scalars(1., 1.),
+            priority: 0,
+        }
+    }
+
+    pub fn with_weight(mut self, weight: ImpedanceVector) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: usize) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    // Weighted error, given the body poses
+    pub fn error(&self, poses: &[PTransform]) -> Vec6 {
+        let weight = self.weight.vector();
+        transform_error(&poses[self.body], &self.target)
+            .vector()
+            .component_mul(&weight)
+    }
+
+    // Weighted Jacobian, given the body poses
+    #[allow(non_snake_case)]
+    pub fn jacobian(&self, mb: &MultiBody, q: &VecX, poses: &[PTransform]) -> Mat6X {
+        let E_body_0 = PTransform::from_mat(poses[self.body].rotation().transpose());
+        let mut jac = E_body_0.matrix() * body_jacobian(mb, q, self.body);
+        for (r, w) in self.weight.vector().iter().enumerate() {
+            jac.row_mut(r).apply(|x| x * w);
+        }
+        jac
+    }
+}
+
+// Bounds of a revolute or prismatic joint
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointLimit {
+    pub joint: usize,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl JointLimit {
+    // Panics if the joint of mb is not revolute or prismatic, the bounds
+    // applying to a single parameter and degree of freedom
+    fn check(&self, mb: &MultiBody) {
+        match mb.joints[self.joint] {
+            JointType::Revolute(_) | JointType::Prismatic(_) => (),
+            ref joint => panic!(
+                "joint limits only apply to revolute and prismatic joints, joint {} is {:?}",
+                self.joint, joint
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IkStatus {
+    // every task error is below the tolerance
+    Converged,
+    // the step became too small before convergence (unreachable target,
+    // conflicting tasks or joint limits)
+    Stalled,
+    MaxIterations,
+}
+
+impl fmt::Display for IkStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IkStatus::Converged => write!(f, "converged"),
+            IkStatus::Stalled => write!(f, "stalled before convergence"),
+            IkStatus::MaxIterations => write!(f, "maximum number of iterations reached"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IkResult {
+    pub q: VecX,
+    pub status: IkStatus,
+    pub iterations: usize,
+    // weighted error norm of each task at q
+    pub errors: Vec<f64>,
+}
+
+impl IkResult {
+    pub fn converged(&self) -> bool {
+        self.status == IkStatus::Converged
+    }
+}
+
+// Damped least squares inverse kinematics
+#[derive(Clone, Debug)]
+pub struct InverseKinematics {
+    pub tasks: Vec<IkTask>,
+    pub joint_limits: Vec<JointLimit>,
+    pub damping: f64,
+    pub tolerance: f64,
+    pub min_step: f64,
+    pub max_iterations: usize,
+    // bound on the norm of the weighted task errors used at each iteration,
+    // limiting the steps toward far targets
+    pub max_error: Option<f64>,
+}
+
+impl InverseKinematics {
+    pub fn new(damping: f64) -> Self {
+        Self {
+            tasks: Vec::new(),
+            joint_limits: Vec::new(),
+            damping,
+            tolerance: 1e-8,
+            min_step: 1e-12,
+            max_iterations: 100,
+            max_error: None,
+        }
+    }
+
+    pub fn with_task(mut self, task: IkTask) -> Self {
+        self.tasks.push(task);
+        self
+    }
+
+    // Panics if the joint of mb is not revolute or prismatic
+    pub fn with_joint_limit(
+        mut self,
+        mb: &MultiBody,
+        joint: usize,
+        lower: f64,
+        upper: f64,
+    ) -> Self {
+        let limit = JointLimit {
+            joint,
+            lower,
+            upper,
+        };
+        limit.check(mb);
+        self.joint_limits.push(limit);
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn with_max_error(mut self, max_error: f64) -> Self {
+        self.max_error = Some(max_error);
+        self
+    }
+
+    pub fn solve(&self, mb: &MultiBody, q_init: &VecX) -> IkResult {
+        for limit in &self.joint_limits {
+            limit.check(mb);
+        }
+        let mut q = self.clamp(mb, q_init);
+        let mut iterations = 0;
+        let mut stalled = false;
+        let status = loop {
+            let errors = self.errors(mb, &q);
+            if errors.iter().all(|e| *e < self.tolerance) {
+                break IkStatus::Converged;
+            }
+            if stalled {
+                break IkStatus::Stalled;
+            }
+            if iterations == self.max_iterations {
+                break IkStatus::MaxIterations;
+            }
+            iterations += 1;
+
+            let step = self.step(mb, &q);
+            let next = self.clamp(mb, &mb.integrate(&q, &step, 1.));
+            stalled = (&next - &q).norm() < self.min_step;
+            q = next;
+        };
+
+        IkResult {
+            errors: self.errors(mb, &q),
+            q,
+            status,
+            iterations,
+        }
+    }
+
+    fn errors(&self, mb: &MultiBody, q: &VecX) -> Vec<f64> {
+        let poses = forward_kinematics(mb, q);
+        self.tasks
+            .iter()
+            .map(|task| task.error(&poses).norm())
+            .collect()
+    }
+
+    // Joint step, locking the joints that would be pushed beyond their limits
+    fn step(&self, mb: &MultiBody, q: &VecX) -> VecX {
+        let mut locked = vec![false; mb.nr_dof()];
+        loop {
+            let step = self.prioritized_step(mb, q, &locked);
+            let mut newly_locked = false;
+            for limit in &self.joint_limits {
+                let (param, dof) = (mb.params_offset(limit.joint), mb.dof_offset(limit.joint));
+                let pushed_down = q[param] <= limit.lower && step[dof] < 0.;
+                let pushed_up = q[param] >= limit.upper && step[dof] > 0.;
+                if !locked[dof] && (pushed_down || pushed_up) {
+                    locked[dof] = true;
+                    newly_locked = true;
+                }
+            }
+            if !newly_locked {
+                return step;
+            }
+        }
+    }
+
+    // Damped least squares solution of each priority level in the null space of
+    // the previous ones
+    fn prioritized_step(&self, mb: &MultiBody, q: &VecX, locked: &[bool]) -> VecX {
+        let dof = mb.nr_dof();
+        let poses = forward_kinematics(mb, q);
+        let mut priorities: Vec<usize> = self.tasks.iter().map(|task| task.priority).collect();
+        priorities.sort();
+        priorities.dedup();
+
+        let mut step = VecX::zeros(dof);
+        let mut null_space = MatX::identity(dof, dof);
+        for (i, is_locked) in locked.iter().enumerate() {
+            if *is_locked {
+                null_space[(i, i)] = 0.;
+            }
+        }
+
+        for priority in priorities {
+            let tasks: Vec<&IkTask> = self
+                .tasks
+                .iter()
+                .filter(|task| task.priority == priority)
+                .collect();
+            let rows = 6 * tasks.len();
+            let mut jac = MatX::zeros(rows, dof);
+            let mut error = VecX::zeros(rows);
+            for (k, task) in tasks.iter().enumerate() {
+                let task_jac = task.jacobian(mb, q, &poses);
+                jac.rows_mut(6 * k, 6)
+                    .copy_from(&MatX::from_fn(6, dof, |r, c| task_jac[(r, c)]));
+                let task_error = task.error(&poses);
+                let norm = task_error.norm();
+                let task_error = match self.max_error {
+                    Some(max_error) if norm > max_error => task_error * (max_error / norm),
+                    _ => task_error,
+                };
+                error.rows_mut(6 * k, 6).copy_from(&task_error);
+            }
+
+            let projected = &jac * &null_space;
+            let residual = error - &jac * &step;
+            step += damped_pseudo_inverse(&projected, self.damping) * residual;
+            null_space -= projected.clone().pseudo_inverse(1e-9) * projected;
+        }
+        step
+    }
+
+    fn clamp(&self, mb: &MultiBody, q: &VecX) -> VecX {
+        let mut q = q.clone();
+        for limit in &self.joint_limits {
+            let param = mb.params_offset(limit.joint);
+            q[param] = q[param].clamp(limit.lower, limit.upper);
+        }
+        q
+    }
+}
+
+// J^T (J J^T + damping^2 I)^-1
+pub fn damped_pseudo_inverse(jac: &MatX, damping: f64) -> MatX {
+    let damped = jac * jac.transpose() + MatX::identity(jac.nrows(), jac.nrows()) * damping.powi(2);
+    match damped.clone().cholesky() {
+        Some(chol) => jac.transpose() * chol.inverse(),
+        None => jac.transpose() * damped.pseudo_inverse(1e-12),
+    }
+}
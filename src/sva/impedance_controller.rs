@@ -0,0 +1,76 @@
+use sva::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GainFrame {
+    // gains are applied on the error expressed in the reference frame orientation
+    Reference,
+    // gains are applied on the error expressed in the current frame
+    Current,
+}
+
+// Cartesian impedance controller.
+// Poses are given as X_ref_frame, velocities as body velocities (i.e.
+// expressed in their own frame) and the output force is expressed in the
+// current frame.
+#[derive(Clone, Copy, Debug)]
+pub struct ImpedanceController {
+    pub stiffness: ImpedanceVector,
+    pub damping: ImpedanceVector,
+    pub gain_frame: GainFrame,
+}
+
+impl ImpedanceController {
+    pub fn new(stiffness: ImpedanceVector, damping: ImpedanceVector) -> Self {
+        Self {
+            stiffness,
+            damping,
+            gain_frame: GainFrame::Reference,
+        }
+    }
+
+    pub fn critically_damped(stiffness: ImpedanceVector, inertia: ImpedanceVector) -> Self {
+        ImpedanceController::new(stiffness, critical_damping(&stiffness, &inertia))
+    }
+
+    pub fn with_gain_frame(mut self, gain_frame: GainFrame) -> Self {
+        self.gain_frame = gain_frame;
+        self
+    }
+
+    #[allow(non_snake_case)]
+    pub fn compute(
+        &self,
+        X_ref_current: &PTransform,
+        X_ref_desired: &PTransform,
+        current_velocity: &MotionVector,
+        desired_velocity: &MotionVector,
+    ) -> ForceVector {
+        let E_current_ref = PTransform::from_mat(X_ref_current.rotation().transpose());
+        let E_desired_ref = PTransform::from_mat(X_ref_desired.rotation().transpose());
+
+        // pose and velocity errors in the reference frame orientation
+        let pose_error = transform_error(X_ref_current, X_ref_desired);
+        let velocity_error = &E_desired_ref * desired_velocity - &E_current_ref * current_velocity;
+
+        match self.gain_frame {
+            GainFrame::Reference => E_current_ref
+                .trans_mul(&(self.stiffness * pose_error + self.damping * velocity_error)),
+            GainFrame::Current => {
+                let E_ref_current = E_current_ref.inv();
+                self.stiffness * (E_ref_current * pose_error)
+                    + self.damping * (E_ref_current * velocity_error)
+            }
+        }
+    }
+}
+
+// Damping giving a critically damped response for the given stiffness and
+// apparent inertia: d = 2 * sqrt(k * m)
+pub fn critical_damping(stiffness: &ImpedanceVector, inertia: &ImpedanceVector) -> ImpedanceVector {
+    ImpedanceVector::from_vector(
+        2. * stiffness
+            .vector()
+            .component_mul(&inertia.vector())
+            .map(libm::sqrt),
+    )
+}
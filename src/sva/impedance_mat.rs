@@ -0,0 +1,114 @@
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::fmt;
+use std::ops;
+
+use sva::*;
+
+// Symmetric positive semi-definite impedance, maps a MotionVector to a ForceVector.
+// from_matrix only accepts positive-definite matrices but diagonal conversions,
+// sums and products by non-negative scalars may be singular (e.g. a zero
+// stiffness), in which case inverse() returns None.
+#[derive(Clone, Copy, Debug)]
+pub struct ImpedanceMatrix {
+    matrix: Mat6,
+}
+
+impl ImpedanceMatrix {
+    pub fn identity() -> Self {
+        Self {
+            matrix: Mat6::identity(),
+        }
+    }
+
+    // Returns None if the matrix is not symmetric positive-definite
+    pub fn from_matrix(matrix: Mat6) -> Option<Self> {
+        if is_symmetric_positive_definite(&matrix) {
+            Some(Self { matrix })
+        } else {
+            None
+        }
+    }
+
+    // The matrix must be symmetric positive semi-definite
+    pub fn from_matrix_unchecked(matrix: Mat6) -> Self {
+        Self { matrix }
+    }
+
+    pub fn from_blocks(angular: Mat3, coupling: Mat3, linear: Mat3) -> Option<Self> {
+        ImpedanceMatrix::from_matrix(mat6_from_blocks(&angular, &coupling, &linear))
+    }
+
+    pub fn matrix(&self) -> Mat6 {
+        self.matrix
+    }
+
+    pub fn inverse(&self) -> Option<AdmittanceMatrix> {
+        self.matrix
+            .cholesky()
+            .map(|chol| AdmittanceMatrix::from_matrix_unchecked(chol.inverse()))
+    }
+}
+
+// Diagonal matrix, the components must be non-negative
+impl TryFrom<ImpedanceVector> for ImpedanceMatrix {
+    type Error = NegativeComponentError;
+
+    fn try_from(impedance_vector: ImpedanceVector) -> Result<Self, Self::Error> {
+        let diagonal = impedance_vector.vector();
+        if diagonal.iter().any(|&x| x < 0.) {
+            Err(NegativeComponentError)
+        } else {
+            let matrix = Mat6::from_diagonal(&diagonal);
+            Ok(ImpedanceMatrix::from_matrix_unchecked(matrix))
+        }
+    }
+}
+
+impl ops::Add<ImpedanceMatrix> for ImpedanceMatrix {
+    type Output = ImpedanceMatrix;
+
+    // the sum of positive semi-definite matrices remains so
+    fn add(self, other: ImpedanceMatrix) -> ImpedanceMatrix {
+        ImpedanceMatrix::from_matrix_unchecked(self.matrix + other.matrix)
+    }
+}
+
+impl ops::Mul<f64> for ImpedanceMatrix {
+    type Output = ImpedanceMatrix;
+
+    // Panics if the scalar is negative
+    fn mul(self, scalar: f64) -> ImpedanceMatrix {
+        assert!(scalar >= 0., "the scalar must be non-negative");
+        ImpedanceMatrix::from_matrix_unchecked(scalar * self.matrix)
+    }
+}
+
+impl ops::Mul<ImpedanceMatrix> for f64 {
+    type Output = ImpedanceMatrix;
+
+    fn mul(self, impedance_matrix: ImpedanceMatrix) -> ImpedanceMatrix {
+        impedance_matrix * self
+    }
+}
+
+impl ops::Mul<MotionVector> for ImpedanceMatrix {
+    type Output = ForceVector;
+
+    fn mul(self, motion_vector: MotionVector) -> ForceVector {
+        ForceVector::from_vector(self.matrix * motion_vector.vector())
+    }
+}
+
+impl std::cmp::PartialEq for ImpedanceMatrix {
+    fn eq(&self, other: &ImpedanceMatrix) -> bool {
+        self.matrix == other.matrix
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ImpedanceMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.matrix)
+    }
+}
@@ -1,14 +1,24 @@
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
 use std::fmt;
 use std::ops;
 
 use sva::*;
 
+// repr(C) stores the six components contiguously, see AsRef<[f64]>
 #[derive(Clone, Copy, Debug)]
+#[repr(C)]
 pub struct ImpedanceVector {
     pub angular: Vec3,
     pub linear: Vec3,
 }
 
+impl Default for ImpedanceVector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ImpedanceVector {
     pub fn zero() -> Self {
         Self {
@@ -30,8 +40,8 @@ impl ImpedanceVector {
 
     pub fn from_vectors(angular: Vec3, linear: Vec3) -> Self {
         Self {
-            angular: angular,
-            linear: linear,
+            angular,
+            linear,
         }
     }
 
@@ -53,6 +63,20 @@ impl ImpedanceVector {
         )
     }
 
+    // Panics if the slice does not hold 6 elements
+    pub fn from_slice(slice: &[f64]) -> Self {
+        assert_eq!(slice.len(), 6);
+        Self {
+            angular: Vec3::new(slice[0], slice[1], slice[2]),
+            linear: Vec3::new(slice[3], slice[4], slice[5]),
+        }
+    }
+
+    // Panics if the slice does not hold 6 elements
+    pub fn copy_to_slice(&self, slice: &mut [f64]) {
+        slice.copy_from_slice(self.as_ref());
+    }
+
     pub fn cross(&self, other: ImpedanceVector) -> ImpedanceVector {
         ImpedanceVector::from_vectors(
             self.angular.cross(&other.angular),
@@ -70,6 +94,21 @@ impl ImpedanceVector {
     pub fn dot(&self, other: ForceVector) -> f64 {
         self.angular.dot(&other.couple) + self.linear.dot(&other.force)
     }
+
+    // Returns None if any of the components is zero
+    pub fn inverse(&self) -> Option<AdmittanceVector> {
+        let angular = component_inverse(&self.angular)?;
+        let linear = component_inverse(&self.linear)?;
+        Some(AdmittanceVector::from_vectors(angular, linear))
+    }
+}
+
+impl TryFrom<AdmittanceVector> for ImpedanceVector {
+    type Error = ZeroComponentError;
+
+    fn try_from(admittance_vector: AdmittanceVector) -> Result<Self, Self::Error> {
+        admittance_vector.inverse().ok_or(ZeroComponentError)
+    }
 }
 
 impl ops::Add<ImpedanceVector> for ImpedanceVector {
@@ -174,6 +213,39 @@ impl std::cmp::PartialEq for ImpedanceVector {
     }
 }
 
+impl From<Vec6> for ImpedanceVector {
+    fn from(vector: Vec6) -> Self {
+        ImpedanceVector::from_vector(vector)
+    }
+}
+
+impl From<ImpedanceVector> for Vec6 {
+    fn from(vector: ImpedanceVector) -> Self {
+        vector.vector()
+    }
+}
+
+impl From<[f64; 6]> for ImpedanceVector {
+    fn from(array: [f64; 6]) -> Self {
+        ImpedanceVector::from_slice(&array)
+    }
+}
+
+impl AsRef<[f64]> for ImpedanceVector {
+    fn as_ref(&self) -> &[f64] {
+        vec3_pair_as_slice(self)
+    }
+}
+
+impl ops::Index<usize> for ImpedanceVector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.as_ref()[index]
+    }
+}
+
+#[cfg(feature = "std")]
 impl fmt::Display for ImpedanceVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
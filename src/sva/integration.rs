@@ -0,0 +1,227 @@
+use sva::*;
+
+// Integration of poses X_0_b and of tree configurations.
+// Body velocities are expressed in the moving frame b and world velocities in
+// the world frame 0. Transformations are updated on SE(3) so that the rotation
+// remains orthonormal.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VelocityFrame {
+    Body,
+    World,
+}
+
+#[allow(non_snake_case)]
+fn body_velocity(
+    X_0_b: &PTransform,
+    velocity: &MotionVector,
+    frame: VelocityFrame,
+) -> MotionVector {
+    match frame {
+        VelocityFrame::Body => *velocity,
+        VelocityFrame::World => *X_0_b * *velocity,
+    }
+}
+
+// Pose reached by moving with the constant velocity during dt
+#[allow(non_snake_case)]
+pub fn integrate_transform(
+    X_0_b: &PTransform,
+    velocity: &MotionVector,
+    frame: VelocityFrame,
+    dt: f64,
+) -> PTransform {
+    transform_exp(&(body_velocity(X_0_b, velocity, frame) * dt)) * *X_0_b
+}
+
+// Explicit Euler step of the pose and of the velocity, the acceleration being
+// expressed in the same frame as the velocity
+#[allow(non_snake_case)]
+pub fn transform_euler_step(
+    X_0_b: &PTransform,
+    velocity: &MotionVector,
+    acceleration: &MotionVector,
+    frame: VelocityFrame,
+    dt: f64,
+) -> (PTransform, MotionVector) {
+    (
+        integrate_transform(X_0_b, velocity, frame, dt),
+        *velocity + *acceleration * dt,
+    )
+}
+
+// Semi-implicit Euler step: the pose is updated with the new velocity
+#[allow(non_snake_case)]
+pub fn transform_semi_implicit_euler_step(
+    X_0_b: &PTransform,
+    velocity: &MotionVector,
+    acceleration: &MotionVector,
+    frame: VelocityFrame,
+    dt: f64,
+) -> (PTransform, MotionVector) {
+    let next_velocity = *velocity + *acceleration * dt;
+    (
+        integrate_transform(X_0_b, &next_velocity, frame, dt),
+        next_velocity,
+    )
+}
+
+// Classical Runge-Kutta 4 step of X_dot = f(t, X) on the rotation matrix and
+// translation, the resulting rotation being projected back onto SO(3)
+#[allow(non_snake_case)]
+pub fn transform_rk4_step<F>(
+    X_0_b: &PTransform,
+    t: f64,
+    dt: f64,
+    frame: VelocityFrame,
+    velocity: F,
+) -> PTransform
+where
+    F: Fn(f64, &PTransform) -> MotionVector,
+{
+    // E_dot = -[w]x * E and p_dot = E^T * v for the body velocity (w, v)
+    let derivative = |t: f64, E: &Mat3, p: &Vec3| {
+        let X = PTransform::from_mat_vec(Rot3::from_matrix_unchecked(*E), *p);
+        let mv = body_velocity(&X, &velocity(t, &X), frame);
+        (
+            -vector3_to_cross_matrix(&mv.angular) * E,
+            E.transpose() * mv.linear,
+        )
+    };
+
+    let E = *X_0_b.rotation().matrix();
+    let p = X_0_b.translation();
+    let (k1_E, k1_p) = derivative(t, &E, &p);
+    let (k2_E, k2_p) = derivative(t + dt / 2., &(E + k1_E * dt / 2.), &(p + k1_p * dt / 2.));
+    let (k3_E, k3_p) = derivative(t + dt / 2., &(E + k2_E * dt / 2.), &(p + k2_p * dt / 2.));
+    let (k4_E, k4_p) = derivative(t + dt, &(E + k3_E * dt), &(p + k3_p * dt));
+
+    let E = E + (k1_E + 2. * k2_E + 2. * k3_E + k4_E) * dt / 6.;
+    let p = p + (k1_p + 2. * k2_p + 2. * k3_p + k4_p) * dt / 6.;
+    PTransform::from_mat_vec(orthonormalize(&E), p)
+}
+
+// Munthe-Kaas Runge-Kutta 4 step of X_dot = f(t, X): the stages are computed
+// in the Lie algebra and mapped onto SE(3) with transform_exp
+#[allow(non_snake_case)]
+pub fn transform_munthe_kaas_rk4_step<F>(
+    X_0_b: &PTransform,
+    t: f64,
+    dt: f64,
+    frame: VelocityFrame,
+    velocity: F,
+) -> PTransform
+where
+    F: Fn(f64, &PTransform) -> MotionVector,
+{
+    let stage = |t: f64, u: &MotionVector| {
+        let X = transform_exp(u) * *X_0_b;
+        dexp_inv(u, &body_velocity(&X, &velocity(t, &X), frame))
+    };
+
+    let k1 = stage(t, &MotionVector::zero());
+    let k2 = stage(t + dt / 2., &(k1 * (dt / 2.)));
+    let k3 = stage(t + dt / 2., &(k2 * (dt / 2.)));
+    let k4 = stage(t + dt, &(k3 * dt));
+    let u = (k1 + k2 * 2. + k3 * 2. + k4) * (dt / 6.);
+    transform_exp(&u) * *X_0_b
+}
+
+// Derivative of u such that X(t) = transform_exp(u(t)) * X(0), given the body
+// velocity, truncated to the terms needed by fourth order methods
+fn dexp_inv(u: &MotionVector, velocity: &MotionVector) -> MotionVector {
+    let u_cross_v = u.cross(*velocity);
+    *velocity + u_cross_v * 0.5 + u.cross(u_cross_v) * (1. / 12.)
+}
+
+// Closest rotation matrix in the Frobenius norm
+fn orthonormalize(mat: &Mat3) -> Rot3 {
+    let svd = mat.svd(true, true);
+    let (u, v_t) = (svd.u.unwrap(), svd.v_t.unwrap());
+    Rot3::from_matrix_unchecked(u * v_t)
+}
+
+#[cfg(feature = "std")]
+// Explicit Euler step of the configuration and of the joint velocities
+pub fn configuration_euler_step(
+    mb: &MultiBody,
+    q: &VecX,
+    alpha: &VecX,
+    alpha_dot: &VecX,
+    dt: f64,
+) -> (VecX, VecX) {
+    (mb.integrate(q, alpha, dt), alpha + alpha_dot * dt)
+}
+
+#[cfg(feature = "std")]
+// Semi-implicit Euler step: the configuration is updated with the new velocities
+pub fn configuration_semi_implicit_euler_step(
+    mb: &MultiBody,
+    q: &VecX,
+    alpha: &VecX,
+    alpha_dot: &VecX,
+    dt: f64,
+) -> (VecX, VecX) {
+    let next_alpha = alpha + alpha_dot * dt;
+    (mb.integrate(q, &next_alpha, dt), next_alpha)
+}
+
+#[cfg(feature = "std")]
+// Runge-Kutta 4 step of the configuration and of the joint velocities given
+// alpha_dot = f(t, q, alpha), spherical and free joints being integrated on
+// their Lie group as in transform_munthe_kaas_rk4_step
+pub fn configuration_rk4_step<F>(
+    mb: &MultiBody,
+    t: f64,
+    q: &VecX,
+    alpha: &VecX,
+    dt: f64,
+    acceleration: F,
+) -> (VecX, VecX)
+where
+    F: Fn(f64, &VecX, &VecX) -> VecX,
+{
+    let stage = |t: f64, u: &VecX, alpha: &VecX| {
+        let q_stage = mb.integrate(q, u, 1.);
+        (
+            configuration_dexp_inv(mb, u, alpha),
+            acceleration(t, &q_stage, alpha),
+        )
+    };
+
+    let (k1_u, k1_a) = stage(t, &VecX::zeros(mb.nr_dof()), alpha);
+    let (k2_u, k2_a) = stage(
+        t + dt / 2.,
+        &(&k1_u * (dt / 2.)),
+        &(alpha + &k1_a * (dt / 2.)),
+    );
+    let (k3_u, k3_a) = stage(
+        t + dt / 2.,
+        &(&k2_u * (dt / 2.)),
+        &(alpha + &k2_a * (dt / 2.)),
+    );
+    let (k4_u, k4_a) = stage(t + dt, &(&k3_u * dt), &(alpha + &k3_a * dt));
+
+    let u = (k1_u + k2_u * 2. + k3_u * 2. + k4_u) * (dt / 6.);
+    let next_alpha = alpha + (k1_a + k2_a * 2. + k3_a * 2. + k4_a) * (dt / 6.);
+    (mb.integrate(q, &u, 1.), next_alpha)
+}
+
+#[cfg(feature = "std")]
+fn configuration_dexp_inv(mb: &MultiBody, u: &VecX, alpha: &VecX) -> VecX {
+    let mut result = alpha.clone();
+    for (i, joint) in mb.joints.iter().enumerate() {
+        let (offset, dof) = (mb.dof_offset(i), joint.dof());
+        let u_joint = joint.motion(mb.joint_dof(u, i));
+        let alpha_joint = joint.motion(mb.joint_dof(alpha, i));
+        let derivative = dexp_inv(&u_joint, &alpha_joint).vector();
+        match joint {
+            JointType::Spherical => result
+                .rows_mut(offset, dof)
+                .copy_from(&derivative.fixed_rows::<U3>(0)),
+            JointType::Free => result.rows_mut(offset, dof).copy_from(&derivative),
+            _ => {}
+        }
+    }
+    result
+}
@@ -0,0 +1,115 @@
+use sva::*;
+
+// Joint between a parent body and its child.
+// Axes are expressed in the joint frame and must be unit vectors.
+// Spherical joint parameters are a quaternion [w, x, y, z] and free joint
+// parameters a quaternion followed by a translation. Their velocities are the
+// child body angular velocity (resp. body velocity) expressed in the child frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JointType {
+    Fixed,
+    Revolute(Vec3),
+    Prismatic(Vec3),
+    Spherical,
+    Free,
+}
+
+impl JointType {
+    // Number of configuration parameters
+    pub fn nr_params(&self) -> usize {
+        match self {
+            JointType::Fixed => 0,
+            JointType::Revolute(_) | JointType::Prismatic(_) => 1,
+            JointType::Spherical => 4,
+            JointType::Free => 7,
+        }
+    }
+
+    // Number of degrees of freedom
+    pub fn dof(&self) -> usize {
+        match self {
+            JointType::Fixed => 0,
+            JointType::Revolute(_) | JointType::Prismatic(_) => 1,
+            JointType::Spherical => 3,
+            JointType::Free => 6,
+        }
+    }
+
+    pub fn zero_params(&self) -> Vec<f64> {
+        match self {
+            JointType::Spherical => vec![1., 0., 0., 0.],
+            JointType::Free => vec![1., 0., 0., 0., 0., 0., 0.],
+            _ => vec![0.; self.nr_params()],
+        }
+    }
+
+    // Motion subspace, expressed in the child frame
+    pub fn motion_subspace(&self) -> Mat6X {
+        let mut s = Mat6X::zeros(self.dof());
+        match self {
+            JointType::Fixed => {}
+            JointType::Revolute(axis) => s.fixed_slice_mut::<U3, U1>(0, 0).copy_from(axis),
+            JointType::Prismatic(axis) => s.fixed_slice_mut::<U3, U1>(3, 0).copy_from(axis),
+            JointType::Spherical => s
+                .fixed_slice_mut::<U3, U3>(0, 0)
+                .copy_from(&Mat3::identity()),
+            JointType::Free => s.copy_from(&Mat6::identity()),
+        }
+        s
+    }
+
+    // Joint transformation X_joint_child
+    pub fn transform(&self, q: &[f64]) -> PTransform {
+        match self {
+            JointType::Fixed => PTransform::identity(),
+            JointType::Revolute(axis) => PTransform::from_mat(Rot3::new(-q[0] * axis)),
+            JointType::Prismatic(axis) => PTransform::from_vec(q[0] * axis),
+            JointType::Spherical => PTransform::from_quat(params_to_quat(q).inverse()),
+            JointType::Free => {
+                PTransform::from_quat_vec(params_to_quat(q).inverse(), Vec3::new(q[4], q[5], q[6]))
+            }
+        }
+    }
+
+    // Joint velocity expressed as the child body velocity relative to its parent
+    pub fn motion(&self, alpha: &[f64]) -> MotionVector {
+        let s = self.motion_subspace();
+        let mut mv = Vec6::zeros();
+        for (i, a) in alpha.iter().enumerate().take(self.dof()) {
+            mv += s.column(i) * *a;
+        }
+        MotionVector::from_vector(mv)
+    }
+
+    // Parameters reached by moving with the constant velocity alpha during dt
+    #[allow(non_snake_case)]
+    pub fn integrate(&self, q: &[f64], alpha: &[f64], dt: f64) -> Vec<f64> {
+        match self {
+            JointType::Fixed => Vec::new(),
+            JointType::Revolute(_) | JointType::Prismatic(_) => vec![q[0] + alpha[0] * dt],
+            JointType::Spherical => {
+                let w = Vec3::new(alpha[0], alpha[1], alpha[2]);
+                let quat = params_to_quat(q) * Quat::from_scaled_axis(w * dt);
+                quat_to_params(&quat).to_vec()
+            }
+            JointType::Free => {
+                let X_j_c = self.transform(q);
+                let next = transform_exp(&(self.motion(alpha) * dt)) * X_j_c;
+                let quat = Quat::from_rotation_matrix(&next.rotation()).inverse();
+                let t = next.translation();
+                let mut params = quat_to_params(&quat).to_vec();
+                params.extend_from_slice(&[t[0], t[1], t[2]]);
+                params
+            }
+        }
+    }
+}
+
+pub fn params_to_quat(q: &[f64]) -> Quat {
+    Quat::from_quaternion(Quaternion::new(q[0], q[1], q[2], q[3]))
+}
+
+pub fn quat_to_params(quat: &Quat) -> [f64; 4] {
+    let q = quat.quaternion();
+    [q.w, q.i, q.j, q.k]
+}
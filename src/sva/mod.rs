@@ -1,11 +1,29 @@
-use nalgebra::{Matrix3, Matrix6, Rotation3, UnitQuaternion, Vector3, Vector6, U3};
+#[cfg(feature = "std")]
+use nalgebra::{DMatrix, DVector, Dynamic, U1};
+use nalgebra::{
+    Matrix2, Matrix3, Matrix6, MatrixMN, Quaternion, Rotation2, Rotation3, UnitQuaternion, Vector2,
+    Vector3, Vector6, VectorN, U10, U3, U6,
+};
 
+pub type Vec2 = Vector2<f64>;
 pub type Vec3 = Vector3<f64>;
 pub type Vec6 = Vector6<f64>;
+pub type Mat2 = Matrix2<f64>;
 pub type Mat3 = Matrix3<f64>;
 pub type Mat6 = Matrix6<f64>;
+pub type Rot2 = Rotation2<f64>;
 pub type Rot3 = Rotation3<f64>;
 pub type Quat = UnitQuaternion<f64>;
+#[cfg(feature = "std")]
+pub type Mat3X = MatrixMN<f64, U3, Dynamic>;
+#[cfg(feature = "std")]
+pub type Mat6X = MatrixMN<f64, U6, Dynamic>;
+#[cfg(feature = "std")]
+pub type MatX = DMatrix<f64>;
+#[cfg(feature = "std")]
+pub type VecX = DVector<f64>;
+pub type Vec10 = VectorN<f64, U10>;
+pub type Mat6x10 = MatrixMN<f64, U6, U10>;
 
 pub mod utility;
 pub use self::utility::*;
@@ -22,9 +40,102 @@ pub use self::admittance_vec::*;
 pub mod impedance_vec;
 pub use self::impedance_vec::*;
 
+pub mod admittance_mat;
+pub use self::admittance_mat::*;
+
+pub mod impedance_mat;
+pub use self::impedance_mat::*;
+
 pub mod ptransform;
 pub use self::ptransform::*;
 
+pub mod rb_inertia;
+pub use self::rb_inertia::*;
+
+pub mod acceleration;
+pub use self::acceleration::*;
+
+pub mod impedance_controller;
+pub use self::impedance_controller::*;
+
+pub mod admittance_controller;
+pub use self::admittance_controller::*;
+
+#[cfg(feature = "std")]
+pub mod frame_tree;
+#[cfg(feature = "std")]
+pub use self::frame_tree::*;
+
+pub mod centroidal;
+pub use self::centroidal::*;
+
+#[cfg(feature = "std")]
+pub mod contact;
+#[cfg(feature = "std")]
+pub use self::contact::*;
+
+pub mod pressure;
+pub use self::pressure::*;
+
+#[cfg(feature = "std")]
+pub mod payload;
+#[cfg(feature = "std")]
+pub use self::payload::*;
+
+#[cfg(feature = "std")]
+pub mod joint;
+#[cfg(feature = "std")]
+pub use self::joint::*;
+
+#[cfg(feature = "std")]
+pub mod multibody;
+#[cfg(feature = "std")]
+pub use self::multibody::*;
+
+#[cfg(feature = "std")]
+pub mod regressor;
+#[cfg(feature = "std")]
+pub use self::regressor::*;
+
+#[cfg(feature = "std")]
+pub mod ik;
+#[cfg(feature = "std")]
+pub use self::ik::*;
+
+#[cfg(feature = "std")]
+pub mod opspace;
+#[cfg(feature = "std")]
+pub use self::opspace::*;
+
+pub mod integration;
+pub use self::integration::*;
+
+pub mod energy;
+pub use self::energy::*;
+
+pub mod screw;
+pub use self::screw::*;
+
+pub mod dual_quat;
+pub use self::dual_quat::*;
+
+pub mod ad;
+pub use self::ad::*;
+
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub use self::batch::*;
+
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "rayon")]
+pub use self::parallel::*;
+
+pub mod typed;
+
+pub mod planar;
+
 pub fn get_first_vec3(vector: &Vec6) -> Vec3 {
     vector.fixed_rows::<U3>(0).into()
 }
@@ -1,14 +1,23 @@
+#[cfg(feature = "std")]
 use std::fmt;
 use std::ops;
 
 use sva::*;
 
+// repr(C) stores the six components contiguously, see AsRef<[f64]>
 #[derive(Clone, Copy, Debug)]
+#[repr(C)]
 pub struct MotionVector {
     pub angular: Vec3,
     pub linear: Vec3,
 }
 
+impl Default for MotionVector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MotionVector {
     pub fn zero() -> Self {
         Self {
@@ -30,8 +39,8 @@ impl MotionVector {
 
     pub fn from_vectors(angular: Vec3, linear: Vec3) -> Self {
         Self {
-            angular: angular,
-            linear: linear,
+            angular,
+            linear,
         }
     }
 
@@ -46,6 +55,20 @@ impl MotionVector {
         )
     }
 
+    // Panics if the slice does not hold 6 elements
+    pub fn from_slice(slice: &[f64]) -> Self {
+        assert_eq!(slice.len(), 6);
+        Self {
+            angular: Vec3::new(slice[0], slice[1], slice[2]),
+            linear: Vec3::new(slice[3], slice[4], slice[5]),
+        }
+    }
+
+    // Panics if the slice does not hold 6 elements
+    pub fn copy_to_slice(&self, slice: &mut [f64]) {
+        slice.copy_from_slice(self.as_ref());
+    }
+
     pub fn cross(&self, other: MotionVector) -> MotionVector {
         MotionVector::from_vectors(
             self.angular.cross(&other.angular),
@@ -63,6 +86,16 @@ impl MotionVector {
     pub fn dot(&self, other: ForceVector) -> f64 {
         self.angular.dot(&other.couple) + self.linear.dot(&other.force)
     }
+
+    // sqrt(m . (W * m)), the weights being non-negative
+    pub fn weighted_norm(&self, weights: &ImpedanceVector) -> f64 {
+        libm::sqrt(self.dot(*weights * *self))
+    }
+
+    // sqrt(m . (K * m)) for a symmetric positive-definite impedance K
+    pub fn metric_norm(&self, metric: &ImpedanceMatrix) -> f64 {
+        libm::sqrt(self.dot(*metric * *self))
+    }
 }
 
 impl ops::Add<MotionVector> for MotionVector {
@@ -101,6 +134,15 @@ impl ops::Neg for MotionVector {
     }
 }
 
+// Natural pairing with force vectors
+impl ops::Mul<ForceVector> for MotionVector {
+    type Output = f64;
+
+    fn mul(self, fv: ForceVector) -> f64 {
+        self.dot(fv)
+    }
+}
+
 impl ops::Mul<f64> for MotionVector {
     type Output = MotionVector;
 
@@ -117,6 +159,16 @@ impl ops::Mul<MotionVector> for f64 {
     }
 }
 
+// Product by a matrix mapping MotionVector to MotionVector (transformations, cross
+// product matrices, ...), other matrices must go through vector()
+impl ops::Mul<MotionVector> for Mat6 {
+    type Output = MotionVector;
+
+    fn mul(self, vector: MotionVector) -> MotionVector {
+        MotionVector::from_vector(self * vector.vector())
+    }
+}
+
 impl ops::MulAssign<f64> for MotionVector {
     fn mul_assign(&mut self, scalar: f64) {
         *self = MotionVector::from_vectors(scalar * self.angular, scalar * self.linear)
@@ -143,6 +195,39 @@ impl std::cmp::PartialEq for MotionVector {
     }
 }
 
+impl From<Vec6> for MotionVector {
+    fn from(vector: Vec6) -> Self {
+        MotionVector::from_vector(vector)
+    }
+}
+
+impl From<MotionVector> for Vec6 {
+    fn from(vector: MotionVector) -> Self {
+        vector.vector()
+    }
+}
+
+impl From<[f64; 6]> for MotionVector {
+    fn from(array: [f64; 6]) -> Self {
+        MotionVector::from_slice(&array)
+    }
+}
+
+impl AsRef<[f64]> for MotionVector {
+    fn as_ref(&self) -> &[f64] {
+        vec3_pair_as_slice(self)
+    }
+}
+
+impl ops::Index<usize> for MotionVector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.as_ref()[index]
+    }
+}
+
+#[cfg(feature = "std")]
 impl fmt::Display for MotionVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -0,0 +1,346 @@
+use sva::*;
+
+// Kinematic tree of rigid bodies.
+// Body i is attached to its parent (or to the world if None) by joint i and
+// parents always come before their children. transforms[i] is the fixed
+// transformation X_parent_joint from the parent body frame to the joint frame.
+// Configurations (q) and velocities (alpha) are stacked joint by joint.
+#[derive(Clone, Debug)]
+pub struct MultiBody {
+    pub bodies: Vec<RBInertia>,
+    pub joints: Vec<JointType>,
+    pub parents: Vec<Option<usize>>,
+    pub transforms: Vec<PTransform>,
+    params_offsets: Vec<usize>,
+    dof_offsets: Vec<usize>,
+}
+
+impl MultiBody {
+    pub fn new(
+        bodies: Vec<RBInertia>,
+        joints: Vec<JointType>,
+        parents: Vec<Option<usize>>,
+        transforms: Vec<PTransform>,
+    ) -> Self {
+        assert_eq!(bodies.len(), joints.len());
+        assert_eq!(bodies.len(), parents.len());
+        assert_eq!(bodies.len(), transforms.len());
+        for (i, parent) in parents.iter().enumerate() {
+            assert!(
+                parent.is_none_or(|p| p < i),
+                "parents must come before their children"
+            );
+        }
+
+        let mut params_offsets = vec![0];
+        let mut dof_offsets = vec![0];
+        for joint in &joints {
+            params_offsets.push(params_offsets.last().unwrap() + joint.nr_params());
+            dof_offsets.push(dof_offsets.last().unwrap() + joint.dof());
+        }
+
+        Self {
+            bodies,
+            joints,
+            parents,
+            transforms,
+            params_offsets,
+            dof_offsets,
+        }
+    }
+
+    pub fn nr_bodies(&self) -> usize {
+        self.bodies.len()
+    }
+
+    pub fn nr_params(&self) -> usize {
+        *self.params_offsets.last().unwrap()
+    }
+
+    pub fn nr_dof(&self) -> usize {
+        *self.dof_offsets.last().unwrap()
+    }
+
+    pub fn params_offset(&self, joint: usize) -> usize {
+        self.params_offsets[joint]
+    }
+
+    pub fn dof_offset(&self, joint: usize) -> usize {
+        self.dof_offsets[joint]
+    }
+
+    pub fn joint_params<'a>(&self, q: &'a VecX, joint: usize) -> &'a [f64] {
+        &q.as_slice()[self.params_offsets[joint]..self.params_offsets[joint + 1]]
+    }
+
+    pub fn joint_dof<'a>(&self, alpha: &'a VecX, joint: usize) -> &'a [f64] {
+        &alpha.as_slice()[self.dof_offsets[joint]..self.dof_offsets[joint + 1]]
+    }
+
+    pub fn zero_params(&self) -> VecX {
+        VecX::from_iterator(
+            self.nr_params(),
+            self.joints.iter().flat_map(|joint| joint.zero_params()),
+        )
+    }
+
+    // The body followed by its ancestors up to the root
+    pub fn ancestors(&self, body: usize) -> Vec<usize> {
+        let mut ancestors = vec![body];
+        while let Some(parent) = self.parents[*ancestors.last().unwrap()] {
+            ancestors.push(parent);
+        }
+        ancestors
+    }
+
+    // Configuration reached by moving with the constant velocity alpha during dt
+    pub fn integrate(&self, q: &VecX, alpha: &VecX, dt: f64) -> VecX {
+        VecX::from_iterator(
+            self.nr_params(),
+            self.joints.iter().enumerate().flat_map(|(i, joint)| {
+                joint.integrate(self.joint_params(q, i), self.joint_dof(alpha, i), dt)
+            }),
+        )
+    }
+
+    // Transformations X_parent_i, the parent being the world for root bodies
+    pub fn parent_to_body(&self, q: &VecX) -> Vec<PTransform> {
+        let mut transforms = Vec::with_capacity(self.nr_bodies());
+        self.parent_to_body_into(q, &mut transforms);
+        transforms
+    }
+
+    fn parent_to_body_into(&self, q: &VecX, transforms: &mut Vec<PTransform>) {
+        transforms.clear();
+        transforms.extend(
+            (0..self.nr_bodies())
+                .map(|i| self.joints[i].transform(self.joint_params(q, i)) * self.transforms[i]),
+        );
+    }
+}
+
+// Buffers reused by successive kinematics and dynamics evaluations to avoid
+// allocations. The free functions below allocate a new workspace on each call.
+#[derive(Clone, Debug, Default)]
+pub struct MultiBodyWorkspace {
+    parent_to_body: Vec<PTransform>,
+    poses: Vec<PTransform>,
+    velocities: Vec<MotionVector>,
+    accelerations: Vec<MotionVector>,
+    forces: Vec<ForceVector>,
+}
+
+impl MultiBodyWorkspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Body poses X_0_i
+    #[allow(non_snake_case)]
+    pub fn forward_kinematics(&mut self, mb: &MultiBody, q: &VecX) -> &[PTransform] {
+        mb.parent_to_body_into(q, &mut self.parent_to_body);
+        self.poses.clear();
+        for (i, X_p_i) in self.parent_to_body.iter().enumerate() {
+            let pose = match mb.parents[i] {
+                Some(parent) => *X_p_i * self.poses[parent],
+                None => *X_p_i,
+            };
+            self.poses.push(pose);
+        }
+        &self.poses
+    }
+
+    // Body velocities, expressed in the body frames
+    pub fn forward_velocity(&mut self, mb: &MultiBody, q: &VecX, alpha: &VecX) -> &[MotionVector] {
+        self.propagate(mb, q, alpha, None, &Vec3::zeros());
+        &self.velocities
+    }
+
+    // Jacobian mapping alpha to the body velocity, expressed in the body frame
+    #[allow(non_snake_case)]
+    pub fn body_jacobian(&mut self, mb: &MultiBody, q: &VecX, body: usize) -> Mat6X {
+        self.forward_kinematics(mb, q);
+        let mut jac = Mat6X::zeros(mb.nr_dof());
+        for joint in mb.ancestors(body) {
+            let X_joint_body = (self.poses[body] * self.poses[joint].inv()).matrix();
+            let s = mb.joints[joint].motion_subspace();
+            let columns = X_joint_body * s;
+            jac.columns_mut(mb.dof_offset(joint), columns.ncols())
+                .copy_from(&columns);
+        }
+        jac
+    }
+
+    // Body velocities and accelerations of the forward pass of the recursive
+    // Newton-Euler algorithm. Gravity is introduced as a fictitious acceleration
+    // of the world, given in the world frame.
+    pub fn forward_acceleration(
+        &mut self,
+        mb: &MultiBody,
+        q: &VecX,
+        alpha: &VecX,
+        alpha_dot: &VecX,
+        gravity: &Vec3,
+    ) -> (&[MotionVector], &[MotionVector]) {
+        self.propagate(mb, q, alpha, Some(alpha_dot), gravity);
+        (&self.velocities, &self.accelerations)
+    }
+
+    #[allow(non_snake_case)]
+    fn propagate(
+        &mut self,
+        mb: &MultiBody,
+        q: &VecX,
+        alpha: &VecX,
+        alpha_dot: Option<&VecX>,
+        gravity: &Vec3,
+    ) {
+        mb.parent_to_body_into(q, &mut self.parent_to_body);
+        let world_acceleration = MotionVector::from_vectors(Vec3::zeros(), -gravity);
+        self.velocities.clear();
+        self.accelerations.clear();
+        for (i, X_p_i) in self.parent_to_body.iter().enumerate() {
+            let joint = &mb.joints[i];
+            let joint_velocity = joint.motion(mb.joint_dof(alpha, i));
+            let (parent_velocity, parent_acceleration) = match mb.parents[i] {
+                Some(parent) => (
+                    self.velocities[parent],
+                    self.accelerations.get(parent).cloned(),
+                ),
+                None => (MotionVector::zero(), Some(world_acceleration)),
+            };
+            let velocity = *X_p_i * parent_velocity + joint_velocity;
+            if let (Some(alpha_dot), Some(parent_acceleration)) = (alpha_dot, parent_acceleration) {
+                let joint_acceleration = joint.motion(mb.joint_dof(alpha_dot, i));
+                self.accelerations.push(
+                    *X_p_i * parent_acceleration
+                        + joint_acceleration
+                        + velocity.cross(joint_velocity),
+                );
+            }
+            self.velocities.push(velocity);
+        }
+    }
+
+    // Joint torques given the joint accelerations (recursive Newton-Euler)
+    pub fn inverse_dynamics(
+        &mut self,
+        mb: &MultiBody,
+        q: &VecX,
+        alpha: &VecX,
+        alpha_dot: &VecX,
+        gravity: &Vec3,
+    ) -> VecX {
+        self.propagate(mb, q, alpha, Some(alpha_dot), gravity);
+        self.forces.clear();
+        for i in 0..mb.nr_bodies() {
+            let (inertia, velocity) = (mb.bodies[i], self.velocities[i]);
+            self.forces
+                .push(inertia * self.accelerations[i] + velocity.cross_dual(inertia * velocity));
+        }
+
+        let mut torques = VecX::zeros(mb.nr_dof());
+        for i in (0..mb.nr_bodies()).rev() {
+            let s = mb.joints[i].motion_subspace();
+            let tau = s.transpose() * self.forces[i].vector();
+            torques
+                .rows_mut(mb.dof_offset(i), tau.nrows())
+                .copy_from(&tau);
+            if let Some(parent) = mb.parents[i] {
+                let force_in_parent = self.parent_to_body[i].trans_mul(&self.forces[i]);
+                self.forces[parent] += force_in_parent;
+            }
+        }
+        torques
+    }
+
+    // Joint space mass matrix, each column being obtained by inverse dynamics
+    pub fn mass_matrix(&mut self, mb: &MultiBody, q: &VecX) -> MatX {
+        let dof = mb.nr_dof();
+        let zero = VecX::zeros(dof);
+        let mut alpha_dot = VecX::zeros(dof);
+        let mut mass_matrix = MatX::zeros(dof, dof);
+        for j in 0..dof {
+            alpha_dot[j] = 1.;
+            let column = self.inverse_dynamics(mb, q, &zero, &alpha_dot, &Vec3::zeros());
+            mass_matrix.set_column(j, &column);
+            alpha_dot[j] = 0.;
+        }
+        mass_matrix
+    }
+
+    // Joint accelerations given the joint torques
+    pub fn forward_dynamics(
+        &mut self,
+        mb: &MultiBody,
+        q: &VecX,
+        alpha: &VecX,
+        torques: &VecX,
+        gravity: &Vec3,
+    ) -> VecX {
+        let bias = self.inverse_dynamics(mb, q, alpha, &VecX::zeros(mb.nr_dof()), gravity);
+        let chol = self
+            .mass_matrix(mb, q)
+            .cholesky()
+            .expect("the mass matrix must be positive-definite");
+        chol.solve(&(torques - bias))
+    }
+}
+
+// Body poses X_0_i
+pub fn forward_kinematics(mb: &MultiBody, q: &VecX) -> Vec<PTransform> {
+    MultiBodyWorkspace::new().forward_kinematics(mb, q).to_vec()
+}
+
+// Body velocities, expressed in the body frames
+pub fn forward_velocity(mb: &MultiBody, q: &VecX, alpha: &VecX) -> Vec<MotionVector> {
+    MultiBodyWorkspace::new()
+        .forward_velocity(mb, q, alpha)
+        .to_vec()
+}
+
+// Jacobian mapping alpha to the body velocity, expressed in the body frame
+pub fn body_jacobian(mb: &MultiBody, q: &VecX, body: usize) -> Mat6X {
+    MultiBodyWorkspace::new().body_jacobian(mb, q, body)
+}
+
+// See MultiBodyWorkspace::forward_acceleration
+pub fn forward_acceleration(
+    mb: &MultiBody,
+    q: &VecX,
+    alpha: &VecX,
+    alpha_dot: &VecX,
+    gravity: &Vec3,
+) -> (Vec<MotionVector>, Vec<MotionVector>) {
+    let mut workspace = MultiBodyWorkspace::new();
+    let (velocities, accelerations) =
+        workspace.forward_acceleration(mb, q, alpha, alpha_dot, gravity);
+    (velocities.to_vec(), accelerations.to_vec())
+}
+
+// Joint torques given the joint accelerations (recursive Newton-Euler)
+pub fn inverse_dynamics(
+    mb: &MultiBody,
+    q: &VecX,
+    alpha: &VecX,
+    alpha_dot: &VecX,
+    gravity: &Vec3,
+) -> VecX {
+    MultiBodyWorkspace::new().inverse_dynamics(mb, q, alpha, alpha_dot, gravity)
+}
+
+// Joint space mass matrix, each column being obtained by inverse dynamics
+pub fn mass_matrix(mb: &MultiBody, q: &VecX) -> MatX {
+    MultiBodyWorkspace::new().mass_matrix(mb, q)
+}
+
+// Joint accelerations given the joint torques
+pub fn forward_dynamics(
+    mb: &MultiBody,
+    q: &VecX,
+    alpha: &VecX,
+    torques: &VecX,
+    gravity: &Vec3,
+) -> VecX {
+    MultiBodyWorkspace::new().forward_dynamics(mb, q, alpha, torques, gravity)
+}
@@ -0,0 +1,76 @@
+use sva::*;
+
+// Operational space quantities of a task of Jacobian J for a system of joint
+// space mass matrix H.
+// The damped variants replace (J * H^-1 * J^T)^-1 by
+// (J * H^-1 * J^T + damping^2 * I)^-1 to remain defined near singularities.
+// All functions return None if H is not positive-definite or, for the undamped
+// ones, if J is rank deficient.
+
+// H^-1 * J^T computed by Cholesky decomposition of H
+fn mass_inverse_jac_trans(mass_matrix: &MatX, jac: &MatX) -> Option<MatX> {
+    mass_matrix
+        .clone()
+        .cholesky()
+        .map(|chol| chol.solve(&jac.transpose()))
+}
+
+fn damped_inverse(mass_matrix: &MatX, jac: &MatX, damping: f64) -> Option<(MatX, MatX)> {
+    let h_inv_jt = mass_inverse_jac_trans(mass_matrix, jac)?;
+    let rows = jac.nrows();
+    let inv_inertia = jac * &h_inv_jt + MatX::identity(rows, rows) * damping.powi(2);
+    let chol = inv_inertia.cholesky()?;
+    // without damping, reject a numerically singular J * H^-1 * J^T, any
+    // positive damping being left to the caller to regularize it
+    let pivots = chol.l_dirty().diagonal();
+    if damping == 0. && pivots.amin().powi(2) <= 1e-12 * pivots.amax().powi(2) {
+        return None;
+    }
+    // Lambda is symmetric so H^-1 * J^T * Lambda = (Lambda * J * H^-1)^T
+    let jac_bar = chol.solve(&h_inv_jt.transpose()).transpose();
+    Some((chol.inverse(), jac_bar))
+}
+
+// Lambda = (J * H^-1 * J^T)^-1
+pub fn operational_space_inertia(mass_matrix: &MatX, jac: &MatX) -> Option<MatX> {
+    operational_space_inertia_damped(mass_matrix, jac, 0.)
+}
+
+pub fn operational_space_inertia_damped(
+    mass_matrix: &MatX,
+    jac: &MatX,
+    damping: f64,
+) -> Option<MatX> {
+    damped_inverse(mass_matrix, jac, damping).map(|(lambda, _)| lambda)
+}
+
+// Dynamically consistent generalized inverse J_bar = H^-1 * J^T * Lambda
+pub fn dynamically_consistent_inverse(mass_matrix: &MatX, jac: &MatX) -> Option<MatX> {
+    dynamically_consistent_inverse_damped(mass_matrix, jac, 0.)
+}
+
+pub fn dynamically_consistent_inverse_damped(
+    mass_matrix: &MatX,
+    jac: &MatX,
+    damping: f64,
+) -> Option<MatX> {
+    damped_inverse(mass_matrix, jac, damping).map(|(_, jac_bar)| jac_bar)
+}
+
+// Null space projector N = I - J_bar * J acting on joint velocities and
+// accelerations. Joint torques are projected by N^T, which produces no task
+// acceleration.
+pub fn dynamically_consistent_null_space(mass_matrix: &MatX, jac: &MatX) -> Option<MatX> {
+    dynamically_consistent_null_space_damped(mass_matrix, jac, 0.)
+}
+
+pub fn dynamically_consistent_null_space_damped(
+    mass_matrix: &MatX,
+    jac: &MatX,
+    damping: f64,
+) -> Option<MatX> {
+    dynamically_consistent_inverse_damped(mass_matrix, jac, damping).map(|jac_bar| {
+        let dof = jac.ncols();
+        MatX::identity(dof, dof) - jac_bar * jac
+    })
+}
@@ -0,0 +1,68 @@
+use rayon::prelude::*;
+
+use sva::*;
+
+// Evaluation of the multibody routines over slices of configurations on the
+// rayon thread pool. A MultiBodyWorkspace is created for each job rayon splits
+// the inputs into and reused for all the inputs of that job, so a thread may
+// allocate several of them. The results are returned in the order of the
+// inputs, whatever the scheduling.
+
+// Apply f to each input with a workspace shared by the inputs of the same job
+pub fn par_map_with_workspace<T, R, F>(mb: &MultiBody, inputs: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&mut MultiBodyWorkspace, &MultiBody, &T) -> R + Sync + Send,
+{
+    inputs
+        .par_iter()
+        .map_init(MultiBodyWorkspace::new, |workspace, input| {
+            f(workspace, mb, input)
+        })
+        .collect()
+}
+
+pub fn par_forward_kinematics(mb: &MultiBody, qs: &[VecX]) -> Vec<Vec<PTransform>> {
+    par_map_with_workspace(mb, qs, |workspace, mb, q| {
+        workspace.forward_kinematics(mb, q).to_vec()
+    })
+}
+
+pub fn par_forward_velocity(mb: &MultiBody, states: &[(VecX, VecX)]) -> Vec<Vec<MotionVector>> {
+    par_map_with_workspace(mb, states, |workspace, mb, (q, alpha)| {
+        workspace.forward_velocity(mb, q, alpha).to_vec()
+    })
+}
+
+pub fn par_body_jacobian(mb: &MultiBody, qs: &[VecX], body: usize) -> Vec<Mat6X> {
+    par_map_with_workspace(mb, qs, |workspace, mb, q| {
+        workspace.body_jacobian(mb, q, body)
+    })
+}
+
+// Joint torques for each (q, alpha, alpha_dot)
+pub fn par_inverse_dynamics(
+    mb: &MultiBody,
+    states: &[(VecX, VecX, VecX)],
+    gravity: &Vec3,
+) -> Vec<VecX> {
+    par_map_with_workspace(mb, states, |workspace, mb, (q, alpha, alpha_dot)| {
+        workspace.inverse_dynamics(mb, q, alpha, alpha_dot, gravity)
+    })
+}
+
+pub fn par_mass_matrix(mb: &MultiBody, qs: &[VecX]) -> Vec<MatX> {
+    par_map_with_workspace(mb, qs, |workspace, mb, q| workspace.mass_matrix(mb, q))
+}
+
+// Joint accelerations for each (q, alpha, torques)
+pub fn par_forward_dynamics(
+    mb: &MultiBody,
+    states: &[(VecX, VecX, VecX)],
+    gravity: &Vec3,
+) -> Vec<VecX> {
+    par_map_with_workspace(mb, states, |workspace, mb, (q, alpha, torques)| {
+        workspace.forward_dynamics(mb, q, alpha, torques, gravity)
+    })
+}
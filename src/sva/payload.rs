@@ -0,0 +1,83 @@
+use sva::*;
+
+// Identified masses below this value are considered as no payload at all
+const PAYLOAD_MIN_MASS: f64 = 1e-6;
+
+// Payload carried by a force/torque sensor, with the sensor measurement
+// offset. The center of mass and the offset are expressed in the sensor frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Payload {
+    pub mass: f64,
+    pub com: Vec3,
+    pub offset: ForceVector,
+}
+
+impl Payload {
+    pub fn new(mass: f64, com: Vec3, offset: ForceVector) -> Self {
+        Self { mass, com, offset }
+    }
+
+    // Least-squares identification from (E_0_s, measured wrench) samples,
+    // gravity being expressed in the world frame.
+    // Returns None if the samples do not span enough orientations.
+    // An identified mass below PAYLOAD_MIN_MASS, including a negative one
+    // coming from noise, gives a zero mass and center of mass: only the
+    // offset is meaningful in that case.
+    #[allow(non_snake_case)]
+    pub fn identify(samples: &[(Rot3, ForceVector)], gravity: &Vec3) -> Option<Self> {
+        // unknowns: [mass, mass * com, couple offset, force offset]
+        let mut a = MatX::zeros(6 * samples.len(), 10);
+        let mut b = VecX::zeros(6 * samples.len());
+        for (i, (E_0_s, measured)) in samples.iter().enumerate() {
+            let gravity_s = E_0_s * gravity;
+            let row = 6 * i;
+            a.fixed_slice_mut::<U3, U3>(row, 1)
+                .copy_from(&-vector3_to_cross_matrix(&gravity_s));
+            a.fixed_slice_mut::<U3, U3>(row, 4)
+                .copy_from(&Mat3::identity());
+            a.fixed_slice_mut::<U3, U1>(row + 3, 0)
+                .copy_from(&gravity_s);
+            a.fixed_slice_mut::<U3, U3>(row + 3, 7)
+                .copy_from(&Mat3::identity());
+            b.fixed_rows_mut::<U6>(row).copy_from(&measured.vector());
+        }
+
+        let svd = a.svd(true, true);
+        let max_singular_value = svd.singular_values.amax();
+        if svd
+            .singular_values
+            .iter()
+            .any(|&s| s <= 1e-9 * max_singular_value)
+        {
+            return None;
+        }
+        let params = svd.solve(&b, 0.);
+        let first_moment = Vec3::new(params[1], params[2], params[3]);
+        let (mass, com) = if params[0] <= PAYLOAD_MIN_MASS {
+            (0., Vec3::zeros())
+        } else {
+            (params[0], first_moment / params[0])
+        };
+        Some(Payload::new(
+            mass,
+            com,
+            ForceVector::from_vectors(
+                Vec3::new(params[4], params[5], params[6]),
+                Vec3::new(params[7], params[8], params[9]),
+            ),
+        ))
+    }
+
+    // Wrench applied by the payload weight on the sensor, in the sensor frame
+    #[allow(non_snake_case)]
+    pub fn gravity_wrench(&self, E_0_s: &Rot3, gravity: &Vec3) -> ForceVector {
+        let weight = self.mass * (E_0_s * gravity);
+        ForceVector::from_vectors(self.com.cross(&weight), weight)
+    }
+
+    // Measured wrench without the sensor offset and the payload weight
+    #[allow(non_snake_case)]
+    pub fn compensate(&self, E_0_s: &Rot3, measured: &ForceVector, gravity: &Vec3) -> ForceVector {
+        *measured - self.offset - self.gravity_wrench(E_0_s, gravity)
+    }
+}
@@ -0,0 +1,550 @@
+//! Planar spatial vector algebra.
+//!
+//! Motions in the xy plane are described by 3D vectors: the rotation velocity
+//! about z and the linear velocity in the plane. Forces are described by the
+//! couple about z and the force in the plane. The types mirror their 6D
+//! counterparts and `embed`/`project` convert between both:
+//!
+//! ```
+//! use sva::sva::planar::{MotionVector, PTransform};
+//! use sva::sva::Vec2;
+//!
+//! let X_a_b = PTransform::from_mat_vec(sva::sva::planar::rot(0.5), Vec2::new(1., 2.));
+//! let v_a = MotionVector::from_parts(1., Vec2::new(0., 1.));
+//! let v_b = MotionVector::project(&(X_a_b.embed() * v_a.embed()));
+//! assert!((v_b.vector() - (X_a_b * v_a).vector()).norm() < 1e-12);
+//! ```
+
+#[cfg(feature = "std")]
+use std::fmt;
+use std::ops;
+
+use nalgebra::{U1, U2};
+
+use sva::{self, Mat2, Mat3, Rot2, Rot3, Vec2, Vec3};
+
+// z component of a x b
+fn cross(a: &Vec2, b: &Vec2) -> f64 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+// (w * z) x v
+fn perp(w: f64, v: &Vec2) -> Vec2 {
+    Vec2::new(-w * v[1], w * v[0])
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MotionVector {
+    pub angular: f64,
+    pub linear: Vec2,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForceVector {
+    pub couple: f64,
+    pub force: Vec2,
+}
+
+macro_rules! impl_planar_vector {
+    ($name:ident, $first:ident, $second:ident) => {
+        impl Default for $name {
+            fn default() -> Self {
+                $name::zero()
+            }
+        }
+
+        impl $name {
+            pub fn zero() -> Self {
+                Self {
+                    $first: 0.,
+                    $second: Vec2::zeros(),
+                }
+            }
+
+            pub fn new() -> Self {
+                $name::zero()
+            }
+
+            pub fn from_vector(vector: Vec3) -> Self {
+                Self {
+                    $first: vector[0],
+                    $second: Vec2::new(vector[1], vector[2]),
+                }
+            }
+
+            pub fn from_parts($first: f64, $second: Vec2) -> Self {
+                Self { $first, $second }
+            }
+
+            pub fn vector(&self) -> Vec3 {
+                Vec3::new(self.$first, self.$second[0], self.$second[1])
+            }
+        }
+
+        impl ops::Add<$name> for $name {
+            type Output = $name;
+
+            fn add(self, other: $name) -> $name {
+                $name::from_parts(self.$first + other.$first, self.$second + other.$second)
+            }
+        }
+
+        impl ops::AddAssign for $name {
+            fn add_assign(&mut self, other: $name) {
+                *self = *self + other
+            }
+        }
+
+        impl ops::Sub<$name> for $name {
+            type Output = $name;
+
+            fn sub(self, other: $name) -> $name {
+                $name::from_parts(self.$first - other.$first, self.$second - other.$second)
+            }
+        }
+
+        impl ops::SubAssign for $name {
+            fn sub_assign(&mut self, other: $name) {
+                *self = *self - other
+            }
+        }
+
+        impl ops::Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name::from_parts(-self.$first, -self.$second)
+            }
+        }
+
+        impl ops::Mul<f64> for $name {
+            type Output = $name;
+
+            fn mul(self, scalar: f64) -> $name {
+                $name::from_parts(scalar * self.$first, scalar * self.$second)
+            }
+        }
+
+        impl ops::Mul<$name> for f64 {
+            type Output = $name;
+
+            fn mul(self, vector: $name) -> $name {
+                vector * self
+            }
+        }
+
+        impl ops::Div<f64> for $name {
+            type Output = $name;
+
+            fn div(self, scalar: f64) -> $name {
+                $name::from_parts(self.$first / scalar, self.$second / scalar)
+            }
+        }
+
+        impl From<Vec3> for $name {
+            fn from(vector: Vec3) -> Self {
+                $name::from_vector(vector)
+            }
+        }
+
+        impl From<$name> for Vec3 {
+            fn from(vector: $name) -> Self {
+                vector.vector()
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    concat!(
+                        "(",
+                        stringify!($first),
+                        ": {}, ",
+                        stringify!($second),
+                        ": [{} {}])"
+                    ),
+                    self.$first, self.$second[0], self.$second[1]
+                )
+            }
+        }
+    };
+}
+
+impl_planar_vector!(MotionVector, angular, linear);
+impl_planar_vector!(ForceVector, couple, force);
+
+impl MotionVector {
+    pub fn cross(&self, other: MotionVector) -> MotionVector {
+        MotionVector::from_parts(
+            0.,
+            perp(self.angular, &other.linear) - perp(other.angular, &self.linear),
+        )
+    }
+
+    pub fn cross_dual(&self, other: ForceVector) -> ForceVector {
+        ForceVector::from_parts(
+            cross(&self.linear, &other.force),
+            perp(self.angular, &other.force),
+        )
+    }
+
+    pub fn dot(&self, other: ForceVector) -> f64 {
+        self.angular * other.couple + self.linear.dot(&other.force)
+    }
+
+    // Motion about z and in the xy plane
+    pub fn embed(&self) -> sva::MotionVector {
+        sva::MotionVector::from_vectors(
+            Vec3::new(0., 0., self.angular),
+            Vec3::new(self.linear[0], self.linear[1], 0.),
+        )
+    }
+
+    // Components of the motion about z and in the xy plane, the others being
+    // dropped
+    pub fn project(mv: &sva::MotionVector) -> Self {
+        MotionVector::from_parts(mv.angular[2], Vec2::new(mv.linear[0], mv.linear[1]))
+    }
+}
+
+impl ForceVector {
+    pub fn cross(&self, other: MotionVector) -> ForceVector {
+        -other.cross_dual(*self)
+    }
+
+    pub fn dot(&self, other: MotionVector) -> f64 {
+        other.dot(*self)
+    }
+
+    // Couple about z and force in the xy plane
+    pub fn embed(&self) -> sva::ForceVector {
+        sva::ForceVector::from_vectors(
+            Vec3::new(0., 0., self.couple),
+            Vec3::new(self.force[0], self.force[1], 0.),
+        )
+    }
+
+    // Components of the force about z and in the xy plane, the others being
+    // dropped
+    pub fn project(fv: &sva::ForceVector) -> Self {
+        ForceVector::from_parts(fv.couple[2], Vec2::new(fv.force[0], fv.force[1]))
+    }
+}
+
+// Natural pairing with force vectors
+impl ops::Mul<ForceVector> for MotionVector {
+    type Output = f64;
+
+    fn mul(self, fv: ForceVector) -> f64 {
+        self.dot(fv)
+    }
+}
+
+impl ops::Mul<MotionVector> for ForceVector {
+    type Output = f64;
+
+    fn mul(self, mv: MotionVector) -> f64 {
+        self.dot(mv)
+    }
+}
+
+// Rotation E_a_b of angle theta about z, as sva::rot_z
+pub fn rot(theta: f64) -> Rot2 {
+    let s = libm::sin(theta);
+    let c = libm::cos(theta);
+    Rot2::from_matrix_unchecked(Mat2::new(c, s, -s, c))
+}
+
+// Planar Plucker transform X_a_b, with the same conventions as
+// sva::PTransform: rotation is E_a_b and translation is the origin of b
+// expressed in a
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PTransform {
+    rotation: Rot2,
+    translation: Vec2,
+}
+
+impl PTransform {
+    pub fn identity() -> Self {
+        Self {
+            rotation: Rot2::identity(),
+            translation: Vec2::zeros(),
+        }
+    }
+
+    pub fn from_mat_vec(rot: Rot2, trans: Vec2) -> Self {
+        Self {
+            rotation: rot,
+            translation: trans,
+        }
+    }
+
+    pub fn from_mat(rot: Rot2) -> Self {
+        PTransform::from_mat_vec(rot, Vec2::zeros())
+    }
+
+    pub fn from_vec(trans: Vec2) -> Self {
+        PTransform::from_mat_vec(Rot2::identity(), trans)
+    }
+
+    pub fn rotation(&self) -> Rot2 {
+        self.rotation
+    }
+
+    pub fn translation(&self) -> Vec2 {
+        self.translation
+    }
+
+    pub fn matrix(&self) -> Mat3 {
+        let e = self.rotation.matrix();
+        let r = &self.translation;
+        let mut m = Mat3::zeros();
+        m[(0, 0)] = 1.;
+        m.fixed_slice_mut::<U2, U2>(1, 1).copy_from(e);
+        m.fixed_slice_mut::<U2, U1>(1, 0)
+            .copy_from(&(e * Vec2::new(-r[1], r[0])));
+        m
+    }
+
+    pub fn dual_matrix(&self) -> Mat3 {
+        let r = &self.translation;
+        let mut m = Mat3::zeros();
+        m[(0, 0)] = 1.;
+        m[(0, 1)] = r[1];
+        m[(0, 2)] = -r[0];
+        m.fixed_slice_mut::<U2, U2>(1, 1)
+            .copy_from(self.rotation.matrix());
+        m
+    }
+
+    pub fn inv_mul(&self, mv: &MotionVector) -> MotionVector {
+        MotionVector::from_parts(
+            mv.angular,
+            self.rotation.transpose() * mv.linear - perp(mv.angular, &self.translation),
+        )
+    }
+
+    pub fn dual_mul(&self, fv: &ForceVector) -> ForceVector {
+        ForceVector::from_parts(
+            fv.couple - cross(&self.translation, &fv.force),
+            self.rotation * fv.force,
+        )
+    }
+
+    pub fn trans_mul(&self, fv: &ForceVector) -> ForceVector {
+        let force = self.rotation.transpose() * fv.force;
+        ForceVector::from_parts(fv.couple + cross(&self.translation, &force), force)
+    }
+
+    pub fn inertia_dual_mul(&self, rb_inertia: &RBInertia) -> RBInertia {
+        let r = &self.translation;
+        let h = rb_inertia.momentum - rb_inertia.mass * r;
+        let inertia =
+            rb_inertia.inertia - 2. * r.dot(&rb_inertia.momentum) + rb_inertia.mass * r.dot(r);
+        RBInertia::new(rb_inertia.mass, self.rotation * h, inertia)
+    }
+
+    pub fn inertia_trans_mul(&self, rb_inertia: &RBInertia) -> RBInertia {
+        let r = &self.translation;
+        let e_t_h = self.rotation.transpose() * rb_inertia.momentum;
+        let inertia = rb_inertia.inertia + 2. * r.dot(&e_t_h) + rb_inertia.mass * r.dot(r);
+        RBInertia::new(rb_inertia.mass, e_t_h + rb_inertia.mass * r, inertia)
+    }
+
+    pub fn inv(&self) -> Self {
+        Self {
+            rotation: self.rotation.transpose(),
+            translation: -(self.rotation * self.translation),
+        }
+    }
+
+    // Rotation about z and translation in the xy plane
+    pub fn embed(&self) -> sva::PTransform {
+        let e = self.rotation.matrix();
+        let rotation = Rot3::from_matrix_unchecked(Mat3::new(
+            e[(0, 0)],
+            e[(0, 1)],
+            0.,
+            e[(1, 0)],
+            e[(1, 1)],
+            0.,
+            0.,
+            0.,
+            1.,
+        ));
+        sva::PTransform::from_mat_vec(
+            rotation,
+            Vec3::new(self.translation[0], self.translation[1], 0.),
+        )
+    }
+
+    // The transformation must keep the xy plane, i.e. be made of a rotation
+    // about z and of a translation in the plane, the other components being
+    // dropped
+    #[allow(non_snake_case)]
+    pub fn project(X_a_b: &sva::PTransform) -> Self {
+        let e = X_a_b.rotation();
+        let t = X_a_b.translation();
+        PTransform::from_mat_vec(
+            Rot2::from_matrix_unchecked(e.matrix().fixed_slice::<U2, U2>(0, 0).into_owned()),
+            Vec2::new(t[0], t[1]),
+        )
+    }
+}
+
+impl ops::Mul<PTransform> for PTransform {
+    type Output = PTransform;
+
+    fn mul(self, other: PTransform) -> PTransform {
+        PTransform {
+            rotation: self.rotation * other.rotation,
+            translation: other.translation + other.rotation.transpose() * self.translation,
+        }
+    }
+}
+
+impl ops::Mul<MotionVector> for PTransform {
+    type Output = MotionVector;
+
+    fn mul(self, mv: MotionVector) -> MotionVector {
+        MotionVector::from_parts(
+            mv.angular,
+            self.rotation * (mv.linear + perp(mv.angular, &self.translation)),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for PTransform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.matrix())
+    }
+}
+
+// Planar rigid body inertia, defined by the body mass, its first moment of
+// mass in the plane (momentum = mass * com) and its rotational inertia about
+// the z axis passing through the frame origin
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RBInertia {
+    pub mass: f64,
+    pub momentum: Vec2,
+    pub inertia: f64,
+}
+
+impl RBInertia {
+    pub fn zero() -> Self {
+        RBInertia::new(0., Vec2::zeros(), 0.)
+    }
+
+    pub fn new(mass: f64, momentum: Vec2, inertia: f64) -> Self {
+        Self {
+            mass,
+            momentum,
+            inertia,
+        }
+    }
+
+    // Build the inertia from the rotational inertia about the com
+    pub fn from_com_inertia(mass: f64, com: Vec2, inertia_com: f64) -> Self {
+        RBInertia::new(mass, mass * com, inertia_com + mass * com.dot(&com))
+    }
+
+    pub fn com(&self) -> Vec2 {
+        self.momentum / self.mass
+    }
+
+    pub fn com_inertia(&self) -> f64 {
+        self.inertia - self.momentum.dot(&self.momentum) / self.mass
+    }
+
+    pub fn matrix(&self) -> Mat3 {
+        let h = &self.momentum;
+        Mat3::new(
+            self.inertia,
+            -h[1],
+            h[0],
+            -h[1],
+            self.mass,
+            0.,
+            h[0],
+            0.,
+            self.mass,
+        )
+    }
+
+    // Body whose rotational inertia at the com is zero except about z. Only
+    // the components acting in the plane are meaningful.
+    pub fn embed(&self) -> sva::RBInertia {
+        let com = self.com();
+        let mut inertia_com = Mat3::zeros();
+        inertia_com[(2, 2)] = self.com_inertia();
+        sva::RBInertia::from_com_inertia(self.mass, Vec3::new(com[0], com[1], 0.), inertia_com)
+    }
+
+    // Components of the inertia acting in the xy plane, the others being
+    // dropped
+    pub fn project(rb_inertia: &sva::RBInertia) -> Self {
+        let h = &rb_inertia.momentum;
+        RBInertia::new(
+            rb_inertia.mass,
+            Vec2::new(h[0], h[1]),
+            rb_inertia.inertia[(2, 2)],
+        )
+    }
+}
+
+impl Default for RBInertia {
+    fn default() -> Self {
+        RBInertia::zero()
+    }
+}
+
+impl ops::Add<RBInertia> for RBInertia {
+    type Output = RBInertia;
+
+    fn add(self, other: RBInertia) -> RBInertia {
+        RBInertia::new(
+            self.mass + other.mass,
+            self.momentum + other.momentum,
+            self.inertia + other.inertia,
+        )
+    }
+}
+
+impl ops::AddAssign for RBInertia {
+    fn add_assign(&mut self, other: RBInertia) {
+        *self = *self + other
+    }
+}
+
+impl ops::Mul<f64> for RBInertia {
+    type Output = RBInertia;
+
+    fn mul(self, scalar: f64) -> RBInertia {
+        RBInertia::new(
+            scalar * self.mass,
+            scalar * self.momentum,
+            scalar * self.inertia,
+        )
+    }
+}
+
+impl ops::Mul<MotionVector> for RBInertia {
+    type Output = ForceVector;
+
+    fn mul(self, mv: MotionVector) -> ForceVector {
+        ForceVector::from_parts(
+            self.inertia * mv.angular + cross(&self.momentum, &mv.linear),
+            self.mass * mv.linear + perp(mv.angular, &self.momentum),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for RBInertia {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.matrix())
+    }
+}
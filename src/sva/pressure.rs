@@ -0,0 +1,45 @@
+use sva::*;
+
+// Center of pressure of a wrench on a plane.
+// The wrench is expressed in frame a and the plane is the (x, y) plane of
+// frame p, given by X_a_p, with its normal along z.
+// The returned point lies on the plane and is expressed in frame a.
+// Returns None if the normal force is below min_normal_force.
+#[allow(non_snake_case)]
+pub fn center_of_pressure(
+    wrench: &ForceVector,
+    X_a_p: &PTransform,
+    min_normal_force: f64,
+) -> Option<Vec3> {
+    let wrench_p = X_a_p.dual_mul(wrench);
+    let normal_force = wrench_p.force[2];
+    if normal_force < min_normal_force {
+        return None;
+    }
+    let cop_p = Vec3::new(
+        -wrench_p.couple[1] / normal_force,
+        wrench_p.couple[0] / normal_force,
+        0.,
+    );
+    Some(X_a_p.translation() + X_a_p.rotation().transpose() * cop_p)
+}
+
+// Zero moment point of several contact wrenches on a plane.
+// Each wrench is expressed in its own frame i with poses given as X_0_i and
+// the plane is given by X_0_p. The returned point is expressed in frame 0.
+#[allow(non_snake_case)]
+pub fn zero_moment_point(
+    wrenches: &[ForceVector],
+    poses: &[PTransform],
+    X_0_p: &PTransform,
+    min_normal_force: f64,
+) -> Option<Vec3> {
+    assert_eq!(wrenches.len(), poses.len());
+    let net_wrench = wrenches
+        .iter()
+        .zip(poses)
+        .fold(ForceVector::zero(), |sum, (wrench, X_0_i)| {
+            sum + X_0_i.trans_mul(wrench)
+        });
+    center_of_pressure(&net_wrench, X_0_p, min_normal_force)
+}
@@ -1,8 +1,10 @@
+#[cfg(feature = "std")]
 use std::fmt;
 use std::ops;
 
 use sva::*;
 
+#[derive(Clone, Copy, Debug)]
 pub struct PTransform {
     rotation: Rot3,
     translation: Vec3,
@@ -32,36 +34,44 @@ impl PTransform {
 
     pub fn from_quat_vec(rot: Quat, trans: Vec3) -> Self {
         Self {
-            rotation: rot.to_rotation_matrix().into(),
+            rotation: rot.to_rotation_matrix(),
             translation: trans,
         }
     }
 
     pub fn from_quat(rot: Quat) -> Self {
         Self {
-            rotation: rot.to_rotation_matrix().into(),
+            rotation: rot.to_rotation_matrix(),
             translation: Vec3::zeros(),
         }
     }
 
     pub fn from_vec(trans: Vec3) -> Self {
         Self {
-            rotation: Quat::identity().to_rotation_matrix().into(),
+            rotation: Quat::identity().to_rotation_matrix(),
             translation: trans,
         }
     }
 
+    pub fn rotation(&self) -> Rot3 {
+        self.rotation
+    }
+
+    pub fn translation(&self) -> Vec3 {
+        self.translation
+    }
+
     pub fn matrix(&self) -> Mat6 {
         let mut m = Mat6::zeros();
 
         m.fixed_slice_mut::<U3, U3>(0, 0)
-            .copy_from(&self.rotation.matrix());
+            .copy_from(self.rotation.matrix());
 
         m.fixed_slice_mut::<U3, U3>(3, 0)
             .copy_from(&(-self.rotation.matrix() * vector3_to_cross_matrix(&self.translation)));
 
         m.fixed_slice_mut::<U3, U3>(3, 3)
-            .copy_from(&self.rotation.matrix());
+            .copy_from(self.rotation.matrix());
         m
     }
 
@@ -69,13 +79,13 @@ impl PTransform {
         let mut m = Mat6::zeros();
 
         m.fixed_slice_mut::<U3, U3>(0, 0)
-            .copy_from(&self.rotation.matrix());
+            .copy_from(self.rotation.matrix());
 
         m.fixed_slice_mut::<U3, U3>(0, 3)
             .copy_from(&(-self.rotation.matrix() * vector3_to_cross_matrix(&self.translation)));
 
         m.fixed_slice_mut::<U3, U3>(3, 3)
-            .copy_from(&self.rotation.matrix());
+            .copy_from(self.rotation.matrix());
         m
     }
 
@@ -137,7 +147,46 @@ impl PTransform {
         self.rotation.transpose() * fv.force
     }
 
-    // TODO functions on inertia
+    pub fn inertia_dual_mul(&self, rb_inertia: &RBInertia) -> RBInertia {
+        let e = self.rotation.matrix();
+        let r_cross = vector3_to_cross_matrix(&self.translation);
+        let h = rb_inertia.momentum - rb_inertia.mass * self.translation;
+        let inertia = rb_inertia.inertia
+            + r_cross * vector3_to_cross_matrix(&rb_inertia.momentum)
+            + vector3_to_cross_matrix(&h) * r_cross;
+        RBInertia::new(rb_inertia.mass, e * h, e * inertia * e.transpose())
+    }
+
+    pub fn inertia_trans_mul(&self, rb_inertia: &RBInertia) -> RBInertia {
+        let e = self.rotation.matrix();
+        let r_cross = vector3_to_cross_matrix(&self.translation);
+        let e_t_h = e.transpose() * rb_inertia.momentum;
+        let h = e_t_h + rb_inertia.mass * self.translation;
+        let inertia = e.transpose() * rb_inertia.inertia * e
+            - r_cross * vector3_to_cross_matrix(&e_t_h)
+            - vector3_to_cross_matrix(&h) * r_cross;
+        RBInertia::new(rb_inertia.mass, h, inertia)
+    }
+
+    pub fn impedance_dual_mul(&self, impedance: &ImpedanceMatrix) -> ImpedanceMatrix {
+        let inv_mat = self.inv().matrix();
+        ImpedanceMatrix::from_matrix_unchecked(inv_mat.transpose() * impedance.matrix() * inv_mat)
+    }
+
+    pub fn impedance_trans_mul(&self, impedance: &ImpedanceMatrix) -> ImpedanceMatrix {
+        let mat = self.matrix();
+        ImpedanceMatrix::from_matrix_unchecked(mat.transpose() * impedance.matrix() * mat)
+    }
+
+    pub fn admittance_mul(&self, admittance: &AdmittanceMatrix) -> AdmittanceMatrix {
+        let mat = self.matrix();
+        AdmittanceMatrix::from_matrix_unchecked(mat * admittance.matrix() * mat.transpose())
+    }
+
+    pub fn admittance_inv_mul(&self, admittance: &AdmittanceMatrix) -> AdmittanceMatrix {
+        let inv_mat = self.inv().matrix();
+        AdmittanceMatrix::from_matrix_unchecked(inv_mat * admittance.matrix() * inv_mat.transpose())
+    }
 
     pub fn inv(&self) -> Self {
         Self {
@@ -158,7 +207,7 @@ impl ops::Mul<PTransform> for PTransform {
     }
 }
 
-impl<'a, 'b> ops::Mul<&'b PTransform> for &'a PTransform {
+impl<'b> ops::Mul<&'b PTransform> for &PTransform {
     type Output = PTransform;
 
     fn mul(self, other: &'b PTransform) -> PTransform {
@@ -177,11 +226,11 @@ impl ops::Mul<MotionVector> for PTransform {
     }
 }
 
-impl<'a, 'b> ops::Mul<&'b MotionVector> for &'a PTransform {
+impl<'b> ops::Mul<&'b MotionVector> for &PTransform {
     type Output = MotionVector;
 
     fn mul(self, mv: &'b MotionVector) -> MotionVector {
-        MotionVector::from_vectors(self.angular_mul(&mv), self.linear_mul(&mv))
+        MotionVector::from_vectors(self.angular_mul(mv), self.linear_mul(mv))
     }
 }
 
@@ -191,6 +240,7 @@ impl std::cmp::PartialEq for PTransform {
     }
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for PTransform {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.matrix())
@@ -198,20 +248,20 @@ impl fmt::Display for PTransform {
 }
 
 pub fn rot_x(theta: f64) -> Rot3 {
-    let s = theta.sin();
-    let c = theta.cos();
+    let s = libm::sin(theta);
+    let c = libm::cos(theta);
     Rot3::from_matrix_unchecked(Mat3::new(1., 0., 0., 0., c, s, 0., -s, c))
 }
 
 pub fn rot_y(theta: f64) -> Rot3 {
-    let s = theta.sin();
-    let c = theta.cos();
+    let s = libm::sin(theta);
+    let c = libm::cos(theta);
     Rot3::from_matrix_unchecked(Mat3::new(c, 0., -s, 0., 1., 0., s, 0., c))
 }
 
 pub fn rot_z(theta: f64) -> Rot3 {
-    let s = theta.sin();
-    let c = theta.cos();
+    let s = libm::sin(theta);
+    let c = libm::cos(theta);
     Rot3::from_matrix_unchecked(Mat3::new(c, s, 0., -s, c, 0., 0., 0., 1.))
 }
 
@@ -219,7 +269,7 @@ pub fn rot_z(theta: f64) -> Rot3 {
 pub fn rotation_velocity(E_a_b: &Rot3) -> Vec3 {
     let mut w: Vec3;
     let acos_v = (E_a_b[(0, 0)] + E_a_b[(1, 1)] + E_a_b[(2, 2)] - 1.) * 0.5;
-    let theta = acos_v.max(-1.).min(1.).acos();
+    let theta = libm::acos(acos_v.clamp(-1., 1.));
 
     w = Vec3::new(
         -E_a_b[(2, 1)] + E_a_b[(1, 2)],
@@ -247,12 +297,30 @@ pub fn transform_error(X_a_b: &PTransform, X_a_c: &PTransform) -> MotionVector {
     PTransform::from_mat(X_a_b.rotation.transpose()) * transform_velocity(&X_b_c)
 }
 
+// Transformation X_b_c obtained by moving frame b with the constant body
+// velocity mv during one unit of time (exponential map on SE(3))
+pub fn transform_exp(mv: &MotionVector) -> PTransform {
+    let theta = mv.angular.norm();
+    let theta2 = theta * theta;
+    let (c1, c2) = if theta < 1e-4 {
+        (0.5 - theta2 / 24., 1. / 6. - theta2 / 120.)
+    } else {
+        (
+            (1. - libm::cos(theta)) / theta2,
+            (theta - libm::sin(theta)) / (theta2 * theta),
+        )
+    };
+    let w_cross = vector3_to_cross_matrix(&mv.angular);
+    let v_mat = Mat3::identity() + c1 * w_cross + c2 * w_cross * w_cross;
+    PTransform::from_mat_vec(Rot3::new(-mv.angular), v_mat * mv.linear)
+}
+
 // interpolate between transformations, t must be between 0 and 1
 pub fn interpolate(from: &PTransform, to: &PTransform, t: f64) -> PTransform {
     let q_from = Quat::from_rotation_matrix(&from.rotation);
     let q_to = Quat::from_rotation_matrix(&to.rotation);
     PTransform::from_quat_vec(
         q_from.slerp(&q_to, t),
-        from.translation * t + to.translation * (1. - t),
+        from.translation * (1. - t) + to.translation * t,
     )
 }
@@ -0,0 +1,168 @@
+#[cfg(feature = "std")]
+use std::fmt;
+use std::ops;
+
+use sva::*;
+
+// Rigid body spatial inertia, defined by the body mass, its first moment of
+// mass (momentum = mass * com) and its rotational inertia at the frame origin
+#[derive(Clone, Copy, Debug)]
+pub struct RBInertia {
+    pub mass: f64,
+    pub momentum: Vec3,
+    pub inertia: Mat3,
+}
+
+impl RBInertia {
+    pub fn zero() -> Self {
+        Self {
+            mass: 0.,
+            momentum: Vec3::zeros(),
+            inertia: Mat3::zeros(),
+        }
+    }
+
+    pub fn new(mass: f64, momentum: Vec3, inertia: Mat3) -> Self {
+        Self {
+            mass,
+            momentum,
+            inertia,
+        }
+    }
+
+    // Build the inertia from the rotational inertia expressed at the com
+    pub fn from_com_inertia(mass: f64, com: Vec3, inertia_com: Mat3) -> Self {
+        let com_cross = vector3_to_cross_matrix(&com);
+        Self {
+            mass,
+            momentum: mass * com,
+            inertia: inertia_com + mass * com_cross * com_cross.transpose(),
+        }
+    }
+
+    pub fn com(&self) -> Vec3 {
+        self.momentum / self.mass
+    }
+
+    pub fn com_inertia(&self) -> Mat3 {
+        let com_cross = vector3_to_cross_matrix(&self.com());
+        self.inertia - self.mass * com_cross * com_cross.transpose()
+    }
+
+    // Standard inertial parameters
+    // [m, hx, hy, hz, Ixx, Ixy, Ixz, Iyy, Iyz, Izz]
+    pub fn from_parameters(params: &Vec10) -> Self {
+        let inertia = Mat3::new(
+            params[4], params[5], params[6], params[5], params[7], params[8], params[6], params[8],
+            params[9],
+        );
+        Self {
+            mass: params[0],
+            momentum: Vec3::new(params[1], params[2], params[3]),
+            inertia,
+        }
+    }
+
+    pub fn parameters(&self) -> Vec10 {
+        let i = &self.inertia;
+        Vec10::from_row_slice(&[
+            self.mass,
+            self.momentum[0],
+            self.momentum[1],
+            self.momentum[2],
+            i[(0, 0)],
+            i[(0, 1)],
+            i[(0, 2)],
+            i[(1, 1)],
+            i[(1, 2)],
+            i[(2, 2)],
+        ])
+    }
+
+    // Positive mass and principal moments at the com satisfying the
+    // triangle inequality
+    pub fn is_physically_consistent(&self) -> bool {
+        if self.mass <= 0. {
+            return false;
+        }
+        let moments = self.com_inertia().symmetric_eigenvalues();
+        let tol = 1e-9 * moments.amax().max(1.);
+        moments.iter().all(|&moment| moment > 0.)
+            && moments[0] + moments[1] >= moments[2] - tol
+            && moments[1] + moments[2] >= moments[0] - tol
+            && moments[2] + moments[0] >= moments[1] - tol
+    }
+
+    pub fn matrix(&self) -> Mat6 {
+        let h_cross = vector3_to_cross_matrix(&self.momentum);
+        mat6_from_blocks(&self.inertia, &h_cross, &(self.mass * Mat3::identity()))
+    }
+}
+
+impl Default for RBInertia {
+    fn default() -> Self {
+        RBInertia::zero()
+    }
+}
+
+impl ops::Add<RBInertia> for RBInertia {
+    type Output = RBInertia;
+
+    fn add(self, other: RBInertia) -> RBInertia {
+        RBInertia::new(
+            self.mass + other.mass,
+            self.momentum + other.momentum,
+            self.inertia + other.inertia,
+        )
+    }
+}
+
+impl ops::AddAssign for RBInertia {
+    fn add_assign(&mut self, other: RBInertia) {
+        *self = *self + other
+    }
+}
+
+impl ops::Mul<f64> for RBInertia {
+    type Output = RBInertia;
+
+    fn mul(self, scalar: f64) -> RBInertia {
+        RBInertia::new(
+            scalar * self.mass,
+            scalar * self.momentum,
+            scalar * self.inertia,
+        )
+    }
+}
+
+impl ops::Mul<RBInertia> for f64 {
+    type Output = RBInertia;
+
+    fn mul(self, rb_inertia: RBInertia) -> RBInertia {
+        rb_inertia * self
+    }
+}
+
+impl ops::Mul<MotionVector> for RBInertia {
+    type Output = ForceVector;
+
+    fn mul(self, mv: MotionVector) -> ForceVector {
+        ForceVector::from_vectors(
+            self.inertia * mv.angular + self.momentum.cross(&mv.linear),
+            self.mass * mv.linear - self.momentum.cross(&mv.angular),
+        )
+    }
+}
+
+impl std::cmp::PartialEq for RBInertia {
+    fn eq(&self, other: &RBInertia) -> bool {
+        self.mass == other.mass && self.momentum == other.momentum && self.inertia == other.inertia
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for RBInertia {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.matrix())
+    }
+}
@@ -0,0 +1,63 @@
+use sva::*;
+
+// Matrix K(mv) such that rb_inertia * mv = K(mv) * rb_inertia.parameters()
+fn inertia_product_regressor(mv: &MotionVector) -> Mat6x10 {
+    let w = &mv.angular;
+    let v = &mv.linear;
+    let mut k = Mat6x10::zeros();
+    k.fixed_slice_mut::<U3, U3>(0, 1)
+        .copy_from(&-vector3_to_cross_matrix(v));
+    k.fixed_slice_mut::<U3, U3>(3, 1)
+        .copy_from(&vector3_to_cross_matrix(w));
+    k.fixed_slice_mut::<U3, U1>(3, 0).copy_from(v);
+    k.fixed_slice_mut::<U3, U6>(0, 4)
+        .copy_from(&MatrixMN::<f64, U3, U6>::new(
+            w[0], w[1], w[2], 0., 0., 0., 0., w[0], 0., w[1], w[2], 0., 0., 0., w[0], 0., w[1],
+            w[2],
+        ));
+    k
+}
+
+// Regressor Y such that the body wrench I * a + v x* (I * v) equals
+// Y * rb_inertia.parameters(), with v and a the body velocity and acceleration
+pub fn inertial_regressor(velocity: &MotionVector, acceleration: &MotionVector) -> Mat6x10 {
+    inertia_product_regressor(acceleration)
+        + vector6_to_cross_dual_matrix(&velocity.vector()) * inertia_product_regressor(velocity)
+}
+
+// Regressor Y such that the joint torques equal Y * pi where pi stacks the
+// inertial parameters of all the bodies
+#[allow(non_snake_case)]
+pub fn joint_torque_regressor(
+    mb: &MultiBody,
+    q: &VecX,
+    alpha: &VecX,
+    alpha_dot: &VecX,
+    gravity: &Vec3,
+) -> MatX {
+    let poses = forward_kinematics(mb, q);
+    let (velocities, accelerations) = forward_acceleration(mb, q, alpha, alpha_dot, gravity);
+    let mut regressor = MatX::zeros(mb.nr_dof(), 10 * mb.nr_bodies());
+    for body in 0..mb.nr_bodies() {
+        let body_regressor = inertial_regressor(&velocities[body], &accelerations[body]);
+        for joint in mb.ancestors(body) {
+            // body wrenches are propagated to the joint with X_joint_body^T
+            let X_joint_body = poses[body] * poses[joint].inv();
+            let s = mb.joints[joint].motion_subspace();
+            let block = s.transpose() * X_joint_body.matrix().transpose() * body_regressor;
+            regressor
+                .slice_mut((mb.dof_offset(joint), 10 * body), (block.nrows(), 10))
+                .copy_from(&block);
+        }
+    }
+    regressor
+}
+
+pub fn parameters_vector(bodies: &[RBInertia]) -> VecX {
+    VecX::from_iterator(
+        10 * bodies.len(),
+        bodies
+            .iter()
+            .flat_map(|body| body.parameters().iter().cloned().collect::<Vec<_>>()),
+    )
+}
@@ -0,0 +1,217 @@
+use std::f64;
+#[cfg(feature = "std")]
+use std::fmt;
+
+use sva::*;
+
+// Below this norm, twists and wrenches are considered without rotation
+// (resp. without force)
+const SCREW_EPSILON: f64 = 1e-12;
+
+// Oriented line of unit direction d passing through the point p, described by
+// its Plucker coordinates (d, m) with m = p x d
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PluckerLine {
+    direction: Vec3,
+    moment: Vec3,
+}
+
+impl PluckerLine {
+    pub fn from_point_direction(point: &Vec3, direction: &Vec3) -> Self {
+        let direction = direction.normalize();
+        Self {
+            direction,
+            moment: point.cross(&direction),
+        }
+    }
+
+    // Returns None if the direction is zero or not orthogonal to the moment
+    pub fn from_plucker(direction: &Vec3, moment: &Vec3) -> Option<Self> {
+        let norm = direction.norm();
+        if norm < SCREW_EPSILON || direction.dot(moment).abs() > 1e-9 * norm * moment.norm() {
+            return None;
+        }
+        Some(Self {
+            direction: direction / norm,
+            moment: moment / norm,
+        })
+    }
+
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+
+    pub fn moment(&self) -> Vec3 {
+        self.moment
+    }
+
+    // Point of the line closest to the origin
+    pub fn point(&self) -> Vec3 {
+        self.direction.cross(&self.moment)
+    }
+
+    // Unit rotation about the line
+    pub fn motion(&self) -> MotionVector {
+        MotionVector::from_vectors(self.direction, self.moment)
+    }
+
+    // Unit force along the line
+    pub fn force(&self) -> ForceVector {
+        ForceVector::from_vectors(self.moment, self.direction)
+    }
+
+    // Express the line given in frame a in frame b
+    #[allow(non_snake_case)]
+    pub fn transform(&self, X_a_b: &PTransform) -> Self {
+        let mv = *X_a_b * self.motion();
+        Self {
+            direction: mv.angular,
+            moment: mv.linear,
+        }
+    }
+
+    // Reciprocal product d1 . m2 + d2 . m1, zero when the lines intersect or
+    // are parallel
+    pub fn reciprocal_product(&self, other: &PluckerLine) -> f64 {
+        self.direction.dot(&other.moment) + other.direction.dot(&self.moment)
+    }
+
+    // Angle between the directions, between 0 and pi
+    pub fn angle(&self, other: &PluckerLine) -> f64 {
+        libm::acos(self.direction.dot(&other.direction).clamp(-1., 1.))
+    }
+
+    pub fn distance(&self, other: &PluckerLine) -> f64 {
+        let cross = self.direction.cross(&other.direction);
+        let sin = cross.norm();
+        if sin > 1e-9 {
+            self.reciprocal_product(other).abs() / sin
+        } else {
+            (other.point() - self.point()).cross(&self.direction).norm()
+        }
+    }
+
+    pub fn distance_to_point(&self, point: &Vec3) -> f64 {
+        (point - self.point()).cross(&self.direction).norm()
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for PluckerLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "(direction: [{dx} {dy} {dz}], moment: [{mx} {my} {mz}])",
+            dx = self.direction[0],
+            dy = self.direction[1],
+            dz = self.direction[2],
+            mx = self.moment[0],
+            my = self.moment[1],
+            mz = self.moment[2]
+        )
+    }
+}
+
+// Screw of the given axis and pitch (translation per radian along the axis).
+// Screws of infinite pitch are pure translations (resp. pure couples) along
+// the axis direction and their axis passes through the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Screw {
+    pub axis: PluckerLine,
+    pub pitch: f64,
+}
+
+impl Screw {
+    pub fn new(axis: PluckerLine, pitch: f64) -> Self {
+        Self { axis, pitch }
+    }
+
+    // Decompose a twist into its screw and magnitude, None for a zero twist
+    pub fn from_motion(mv: &MotionVector) -> Option<(Self, f64)> {
+        let (w, v) = (mv.angular, mv.linear);
+        let magnitude = w.norm();
+        if magnitude > SCREW_EPSILON {
+            let direction = w / magnitude;
+            let point = w.cross(&v) / (magnitude * magnitude);
+            let pitch = direction.dot(&v) / magnitude;
+            let axis = PluckerLine::from_point_direction(&point, &direction);
+            Some((Screw::new(axis, pitch), magnitude))
+        } else {
+            Screw::infinite_pitch(&v)
+        }
+    }
+
+    // Decompose a wrench into its screw and magnitude, None for a zero wrench
+    pub fn from_force(fv: &ForceVector) -> Option<(Self, f64)> {
+        let (n, f) = (fv.couple, fv.force);
+        let magnitude = f.norm();
+        if magnitude > SCREW_EPSILON {
+            let direction = f / magnitude;
+            let point = f.cross(&n) / (magnitude * magnitude);
+            let pitch = direction.dot(&n) / magnitude;
+            let axis = PluckerLine::from_point_direction(&point, &direction);
+            Some((Screw::new(axis, pitch), magnitude))
+        } else {
+            Screw::infinite_pitch(&n)
+        }
+    }
+
+    fn infinite_pitch(direction: &Vec3) -> Option<(Self, f64)> {
+        let magnitude = direction.norm();
+        if magnitude > SCREW_EPSILON {
+            let axis = PluckerLine::from_point_direction(&Vec3::zeros(), direction);
+            Some((Screw::new(axis, f64::INFINITY), magnitude))
+        } else {
+            None
+        }
+    }
+
+    pub fn is_infinite_pitch(&self) -> bool {
+        self.pitch.is_infinite()
+    }
+
+    // Twist of the given magnitude along the screw
+    pub fn motion(&self, magnitude: f64) -> MotionVector {
+        let (d, m) = (self.axis.direction, self.axis.moment);
+        if self.is_infinite_pitch() {
+            MotionVector::from_vectors(Vec3::zeros(), magnitude * d)
+        } else {
+            MotionVector::from_vectors(magnitude * d, magnitude * (m + self.pitch * d))
+        }
+    }
+
+    // Wrench of the given magnitude along the screw
+    pub fn force(&self, magnitude: f64) -> ForceVector {
+        let (d, m) = (self.axis.direction, self.axis.moment);
+        if self.is_infinite_pitch() {
+            ForceVector::from_vectors(magnitude * d, Vec3::zeros())
+        } else {
+            ForceVector::from_vectors(magnitude * (m + self.pitch * d), magnitude * d)
+        }
+    }
+
+    // Express the screw given in frame a in frame b
+    #[allow(non_snake_case)]
+    pub fn transform(&self, X_a_b: &PTransform) -> Self {
+        if self.is_infinite_pitch() {
+            let direction = X_a_b.rotation() * self.axis.direction;
+            Screw::new(
+                PluckerLine::from_point_direction(&Vec3::zeros(), &direction),
+                self.pitch,
+            )
+        } else {
+            Screw::new(self.axis.transform(X_a_b), self.pitch)
+        }
+    }
+
+    // Power of the wrench along the unit twist of the screw, zero when the
+    // wrench is reciprocal to the screw
+    pub fn reciprocal_product(&self, fv: &ForceVector) -> f64 {
+        self.motion(1.).dot(*fv)
+    }
+}
+
+// Instantaneous screw axis of a twist, None for a zero twist
+pub fn instantaneous_screw_axis(mv: &MotionVector) -> Option<Screw> {
+    Screw::from_motion(mv).map(|(screw, _)| screw)
+}
@@ -0,0 +1,268 @@
+//! Opt-in frame-checked layer over the spatial vector types.
+//!
+//! Frames are any user-defined marker types. `MotionVector<B>` is a motion
+//! vector expressed in frame `B` and `PTransform<A, B>` is `X_a_b`, so that
+//! operations only compile when the frames match:
+//!
+//! ```
+//! use sva::sva::typed::{MotionVector, PTransform};
+//!
+//! struct World;
+//! struct Hand;
+//!
+//! let X_w_h = PTransform::<World, Hand>::from_untyped(sva::sva::PTransform::identity());
+//! let v_w = MotionVector::<World>::from_untyped(sva::sva::MotionVector::zero());
+//! let v_h: MotionVector<Hand> = X_w_h * v_w;
+//! # let _ = v_h;
+//! ```
+//!
+//! ```compile_fail
+//! use sva::sva::typed::{MotionVector, PTransform};
+//!
+//! struct World;
+//! struct Hand;
+//!
+//! let X_w_h = PTransform::<World, Hand>::from_untyped(sva::sva::PTransform::identity());
+//! let v_h = MotionVector::<Hand>::from_untyped(sva::sva::MotionVector::zero());
+//! let _ = X_w_h * v_h;
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops;
+
+use sva;
+
+#[repr(transparent)]
+pub struct MotionVector<F> {
+    vector: sva::MotionVector,
+    frame: PhantomData<fn() -> F>,
+}
+
+#[repr(transparent)]
+pub struct ForceVector<F> {
+    vector: sva::ForceVector,
+    frame: PhantomData<fn() -> F>,
+}
+
+// X_a_b, maps quantities expressed in frame A to frame B
+#[repr(transparent)]
+pub struct PTransform<A, B> {
+    transform: sva::PTransform,
+    frames: PhantomData<fn() -> (A, B)>,
+}
+
+impl<F> MotionVector<F> {
+    pub fn from_untyped(vector: sva::MotionVector) -> Self {
+        Self {
+            vector,
+            frame: PhantomData,
+        }
+    }
+
+    pub fn zero() -> Self {
+        MotionVector::from_untyped(sva::MotionVector::zero())
+    }
+
+    pub fn untyped(&self) -> sva::MotionVector {
+        self.vector
+    }
+
+    pub fn cross(&self, other: MotionVector<F>) -> MotionVector<F> {
+        MotionVector::from_untyped(self.vector.cross(other.vector))
+    }
+
+    pub fn cross_dual(&self, other: ForceVector<F>) -> ForceVector<F> {
+        ForceVector::from_untyped(self.vector.cross_dual(other.vector))
+    }
+
+    pub fn dot(&self, other: ForceVector<F>) -> f64 {
+        self.vector.dot(other.vector)
+    }
+}
+
+impl<F> ForceVector<F> {
+    pub fn from_untyped(vector: sva::ForceVector) -> Self {
+        Self {
+            vector,
+            frame: PhantomData,
+        }
+    }
+
+    pub fn zero() -> Self {
+        ForceVector::from_untyped(sva::ForceVector::zero())
+    }
+
+    pub fn untyped(&self) -> sva::ForceVector {
+        self.vector
+    }
+
+    pub fn cross(&self, other: MotionVector<F>) -> ForceVector<F> {
+        ForceVector::from_untyped(self.vector.cross(other.vector))
+    }
+
+    pub fn dot(&self, other: MotionVector<F>) -> f64 {
+        self.vector.dot(other.vector)
+    }
+}
+
+impl<A, B> PTransform<A, B> {
+    pub fn from_untyped(transform: sva::PTransform) -> Self {
+        Self {
+            transform,
+            frames: PhantomData,
+        }
+    }
+
+    pub fn untyped(&self) -> sva::PTransform {
+        self.transform
+    }
+
+    pub fn inv(&self) -> PTransform<B, A> {
+        PTransform::from_untyped(self.transform.inv())
+    }
+
+    pub fn inv_mul(&self, mv: &MotionVector<B>) -> MotionVector<A> {
+        MotionVector::from_untyped(self.transform.inv_mul(&mv.vector))
+    }
+
+    pub fn dual_mul(&self, fv: &ForceVector<A>) -> ForceVector<B> {
+        ForceVector::from_untyped(self.transform.dual_mul(&fv.vector))
+    }
+
+    pub fn trans_mul(&self, fv: &ForceVector<B>) -> ForceVector<A> {
+        ForceVector::from_untyped(self.transform.trans_mul(&fv.vector))
+    }
+}
+
+impl<A> PTransform<A, A> {
+    pub fn identity() -> Self {
+        PTransform::from_untyped(sva::PTransform::identity())
+    }
+}
+
+// X_a_c = X_b_c * X_a_b
+impl<A, B, C> ops::Mul<PTransform<A, B>> for PTransform<B, C> {
+    type Output = PTransform<A, C>;
+
+    fn mul(self, other: PTransform<A, B>) -> PTransform<A, C> {
+        PTransform::from_untyped(self.transform * other.transform)
+    }
+}
+
+impl<A, B> ops::Mul<MotionVector<A>> for PTransform<A, B> {
+    type Output = MotionVector<B>;
+
+    fn mul(self, mv: MotionVector<A>) -> MotionVector<B> {
+        MotionVector::from_untyped(self.transform * mv.vector)
+    }
+}
+
+macro_rules! impl_vector_ops {
+    ($name:ident) => {
+        impl<F> Clone for $name<F> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<F> Copy for $name<F> {}
+
+        impl<F> fmt::Debug for $name<F> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.vector.fmt(f)
+            }
+        }
+
+        #[cfg(feature = "std")]
+        impl<F> fmt::Display for $name<F> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.vector)
+            }
+        }
+
+        impl<F> PartialEq for $name<F> {
+            fn eq(&self, other: &$name<F>) -> bool {
+                self.vector == other.vector
+            }
+        }
+
+        impl<F> ops::Add<$name<F>> for $name<F> {
+            type Output = $name<F>;
+
+            fn add(self, other: $name<F>) -> $name<F> {
+                $name::from_untyped(self.vector + other.vector)
+            }
+        }
+
+        impl<F> ops::Sub<$name<F>> for $name<F> {
+            type Output = $name<F>;
+
+            fn sub(self, other: $name<F>) -> $name<F> {
+                $name::from_untyped(self.vector - other.vector)
+            }
+        }
+
+        impl<F> ops::Neg for $name<F> {
+            type Output = $name<F>;
+
+            fn neg(self) -> $name<F> {
+                $name::from_untyped(-self.vector)
+            }
+        }
+
+        impl<F> ops::Mul<f64> for $name<F> {
+            type Output = $name<F>;
+
+            fn mul(self, scalar: f64) -> $name<F> {
+                $name::from_untyped(self.vector * scalar)
+            }
+        }
+
+        impl<F> ops::Mul<$name<F>> for f64 {
+            type Output = $name<F>;
+
+            fn mul(self, vector: $name<F>) -> $name<F> {
+                vector * self
+            }
+        }
+
+        impl<F> ops::Div<f64> for $name<F> {
+            type Output = $name<F>;
+
+            fn div(self, scalar: f64) -> $name<F> {
+                $name::from_untyped(self.vector / scalar)
+            }
+        }
+    };
+}
+
+impl_vector_ops!(MotionVector);
+impl_vector_ops!(ForceVector);
+
+impl<A, B> Clone for PTransform<A, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, B> Copy for PTransform<A, B> {}
+
+impl<A, B> fmt::Debug for PTransform<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.transform.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A, B> fmt::Display for PTransform<A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.transform)
+    }
+}
+
+impl<A, B> PartialEq for PTransform<A, B> {
+    fn eq(&self, other: &PTransform<A, B>) -> bool {
+        self.transform == other.transform
+    }
+}
@@ -1,6 +1,84 @@
 use nalgebra::U3;
+#[cfg(feature = "std")]
+use std::error;
+#[cfg(feature = "std")]
+use std::fmt;
+use std::mem;
+use std::slice;
 use sva::*;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZeroComponentError;
+
+#[cfg(feature = "std")]
+impl fmt::Display for ZeroComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot invert a vector with a zero component")
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for ZeroComponentError {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NegativeComponentError;
+
+#[cfg(feature = "std")]
+impl fmt::Display for NegativeComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot build a positive semi-definite matrix from a vector with a negative component"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for NegativeComponentError {}
+
+// Six-dimensional vectors made of two repr(C) Vec3, viewed as six contiguous
+// components by vec3_pair_as_slice
+pub(crate) trait Vec3Pair {}
+
+impl Vec3Pair for MotionVector {}
+impl Vec3Pair for ForceVector {}
+impl Vec3Pair for ImpedanceVector {}
+impl Vec3Pair for AdmittanceVector {}
+
+// Compile-time check that a Vec3 and each Vec3Pair have the layout of
+// [f64; 3] and [f64; 6], i.e. no padding
+const _: () = {
+    let f64_size = mem::size_of::<f64>();
+    let f64_align = mem::align_of::<f64>();
+    assert!(mem::size_of::<Vec3>() == 3 * f64_size);
+    assert!(mem::align_of::<Vec3>() == f64_align);
+    assert!(mem::size_of::<MotionVector>() == 6 * f64_size);
+    assert!(mem::align_of::<MotionVector>() == f64_align);
+    assert!(mem::size_of::<ForceVector>() == 6 * f64_size);
+    assert!(mem::align_of::<ForceVector>() == f64_align);
+    assert!(mem::size_of::<ImpedanceVector>() == 6 * f64_size);
+    assert!(mem::align_of::<ImpedanceVector>() == f64_align);
+    assert!(mem::size_of::<AdmittanceVector>() == 6 * f64_size);
+    assert!(mem::align_of::<AdmittanceVector>() == f64_align);
+};
+
+pub(crate) fn vec3_pair_as_slice<T: Vec3Pair>(vector: &T) -> &[f64] {
+    // SAFETY: a Vec3 is a repr(C) nalgebra Matrix storing its components in a
+    // [f64; 3] array, and each Vec3Pair is a repr(C) struct of two Vec3. The
+    // asserts above guarantee that neither adds padding, so the pointer is
+    // aligned for f64 and the six components are initialized and contiguous
+    // for the lifetime of the borrow.
+    unsafe { slice::from_raw_parts(vector as *const T as *const f64, 6) }
+}
+
+pub fn component_inverse(vec: &Vec3) -> Option<Vec3> {
+    if vec.iter().any(|&x| x == 0.) {
+        None
+    } else {
+        Some(vec.map(|x| 1. / x))
+    }
+}
+
 pub fn vector3_to_cross_matrix(vec: &Vec3) -> Mat3 {
     Mat3::new(
         0., -vec[2], vec[1], vec[2], 0., -vec[0], -vec[1], vec[0], 0.,
@@ -22,14 +100,29 @@ pub fn vector6_to_cross_dual_matrix(vec: &Vec6) -> Mat6 {
     -vector6_to_cross_matrix(vec).transpose()
 }
 
+pub fn mat6_from_blocks(angular: &Mat3, coupling: &Mat3, linear: &Mat3) -> Mat6 {
+    let mut mat = Mat6::zeros();
+    mat.fixed_slice_mut::<U3, U3>(0, 0).copy_from(angular);
+    mat.fixed_slice_mut::<U3, U3>(0, 3).copy_from(coupling);
+    mat.fixed_slice_mut::<U3, U3>(3, 0)
+        .copy_from(&coupling.transpose());
+    mat.fixed_slice_mut::<U3, U3>(3, 3).copy_from(linear);
+    mat
+}
+
+pub fn is_symmetric_positive_definite(mat: &Mat6) -> bool {
+    let asymmetry = (mat - mat.transpose()).norm();
+    asymmetry <= 1e-10 * mat.norm().max(1.) && mat.cholesky().is_some()
+}
+
 /**
  * Compute 1/sinc(x).
  * This code is inspired by boost/math/special_functions/sinc.hpp.
  */
 pub fn sinc_inv(x: f64) -> f64 {
-    let taylor_0_bound = std::f64::EPSILON;
-    let taylor_2_bound = taylor_0_bound.sqrt();
-    let taylor_n_bound = taylor_2_bound.sqrt();
+    let taylor_0_bound = f64::EPSILON;
+    let taylor_2_bound = libm::sqrt(taylor_0_bound);
+    let taylor_n_bound = libm::sqrt(taylor_2_bound);
 
     // We use the 4th order taylor series around 0 of x/sin(x) to compute
     // this function:
@@ -44,7 +137,7 @@ pub fn sinc_inv(x: f64) -> f64 {
     //       taylor_n_bound^6 + taylor_0_bound != taylor_0).
 
     if x.abs() >= taylor_n_bound {
-        x / x.sin()
+        x / libm::sin(x)
     }
     else {
         let mut result = 1.;
@@ -61,3 +154,59 @@ pub fn sinc_inv(x: f64) -> f64 {
         result
     }
 }
+
+#[cfg(feature = "std")]
+/**
+ * Non-negative least squares: minimize ||a*x - b|| subject to x >= 0.
+ * Implements the Lawson-Hanson active set algorithm.
+ */
+pub fn nnls(a: &MatX, b: &VecX) -> VecX {
+    let n = a.ncols();
+    let tol = 1e-10 * a.norm().max(1.);
+    let mut x = VecX::zeros(n);
+    let mut passive = vec![false; n];
+
+    for _ in 0..3 * n.max(1) {
+        let w = a.transpose() * (b - a * &x);
+        let candidate = (0..n)
+            .filter(|&j| !passive[j] && w[j] > tol)
+            .max_by(|&i, &j| w[i].partial_cmp(&w[j]).unwrap());
+        let j = match candidate {
+            Some(j) => j,
+            None => break,
+        };
+        passive[j] = true;
+
+        loop {
+            let s = passive_least_squares(a, b, &passive);
+            if (0..n).all(|i| !passive[i] || s[i] > 0.) {
+                x = s;
+                break;
+            }
+            let alpha = (0..n)
+                .filter(|&i| passive[i] && s[i] <= 0.)
+                .map(|i| x[i] / (x[i] - s[i]))
+                .fold(1., f64::min);
+            x += alpha * (s - &x);
+            for i in 0..n {
+                if passive[i] && x[i] <= tol {
+                    passive[i] = false;
+                    x[i] = 0.;
+                }
+            }
+        }
+    }
+    x
+}
+
+#[cfg(feature = "std")]
+fn passive_least_squares(a: &MatX, b: &VecX, passive: &[bool]) -> VecX {
+    let indices: Vec<usize> = (0..passive.len()).filter(|&i| passive[i]).collect();
+    let a_passive = MatX::from_fn(a.nrows(), indices.len(), |r, c| a[(r, indices[c])]);
+    let s_passive = a_passive.svd(true, true).solve(b, 1e-12);
+    let mut s = VecX::zeros(passive.len());
+    for (k, &i) in indices.iter().enumerate() {
+        s[i] = s_passive[k];
+    }
+    s
+}
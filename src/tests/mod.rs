@@ -1,5 +1,8 @@
+#[allow(clippy::module_inception)]
 #[cfg(test)]
 mod tests {
+    use nalgebra;
+    use std::convert::TryFrom;
     use std::f64;
     use sva;
 
@@ -373,4 +376,1816 @@ mod tests {
         // zero
         assert_eq!(sva::AdmittanceVector::zero().vector(), sva::Vec6::zeros());
     }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn acceleration_test() {
+        // body trajectory X_0_b(t)
+        let X_0_b = |t: f64| {
+            sva::PTransform::from_mat_vec(
+                sva::rot_x(0.3 * t * t) * sva::rot_z(1.2 * t) * sva::rot_y(-0.7 * t),
+                sva::Vec3::new(t.sin(), 0.5 * t * t, 2. * t),
+            )
+        };
+        // body velocity from central differences of the trajectory
+        let h = 1e-4;
+        let velocity = |t: f64| {
+            let X_0_b_t = X_0_b(t);
+            let fwd = sva::transform_velocity(&(X_0_b(t + h) * X_0_b_t.inv()));
+            let bwd = sva::transform_velocity(&(X_0_b(t - h) * X_0_b_t.inv()));
+            (fwd - bwd) / (2. * h)
+        };
+        let point_position = |t: f64, point: &sva::Vec3| {
+            let X = X_0_b(t);
+            X.translation() + X.rotation().transpose() * point
+        };
+
+        let t = 0.8;
+        let point = sva::Vec3::new(0.2, -0.4, 1.);
+        let vel = velocity(t);
+        let acc = (velocity(t + h) - velocity(t - h)) / (2. * h);
+        let E_0_b = X_0_b(t).rotation();
+
+        let vel_fd = (point_position(t + h, &point) - point_position(t - h, &point)) / (2. * h);
+        let acc_fd = (point_position(t + h, &point) - 2. * point_position(t, &point)
+            + point_position(t - h, &point))
+            / (h * h);
+
+        // body origin
+        let origin = sva::Vec3::zeros();
+        assert!((sva::point_velocity(&vel, &origin) - vel.linear).norm() < TOL);
+        assert!(
+            (sva::point_acceleration(&vel, &acc, &origin)
+                - sva::classical_acceleration(&vel, &acc).linear)
+                .norm()
+                < TOL
+        );
+
+        // arbitrary body point
+        assert!((sva::point_velocity(&vel, &point) - E_0_b * vel_fd).norm() < 1e-4);
+        assert!((sva::point_acceleration(&vel, &acc, &point) - E_0_b * acc_fd).norm() < 1e-4);
+
+        // round trip
+        let classical = sva::classical_acceleration(&vel, &acc);
+        assert_eq!(classical.angular, acc.angular);
+        assert!(
+            (sva::spatial_acceleration(&vel, &classical) - acc)
+                .vector()
+                .norm()
+                < TOL
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn impedance_admittance_matrix_test() {
+        let m = sva::Mat6::new_random();
+        let spd = m * m.transpose() + sva::Mat6::identity();
+
+        // construction
+        assert!(sva::ImpedanceMatrix::from_matrix(spd).is_some());
+        assert!(sva::ImpedanceMatrix::from_matrix(-spd).is_none());
+        assert!(sva::ImpedanceMatrix::from_matrix(spd + m - m.transpose()).is_none());
+        assert!(sva::AdmittanceMatrix::from_matrix(spd).is_some());
+        assert!(sva::AdmittanceMatrix::from_matrix(-spd).is_none());
+
+        let z = sva::ImpedanceMatrix::from_matrix(spd).unwrap();
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let fv = sva::ForceVector::from_vector(sva::Vec6::new_random());
+
+        // operator *
+        assert_eq!((z * mv).vector(), spd * mv.vector());
+        assert_eq!(((2. * z) * mv).vector(), 2. * spd * mv.vector());
+
+        // diagonal conversion
+        let zv = sva::ImpedanceVector::from_vector(sva::Vec6::new_random());
+        assert_eq!(sva::ImpedanceMatrix::try_from(zv).unwrap() * mv, zv * mv);
+        let yv = sva::AdmittanceVector::from_vector(sva::Vec6::new_random());
+        assert_eq!(sva::AdmittanceMatrix::try_from(yv).unwrap() * fv, yv * fv);
+
+        // inversion
+        let y = z.inverse().unwrap();
+        assert!(((y * (z * mv)) - mv).vector().norm() < TOL);
+        assert!(((z * (y * fv)) - fv).vector().norm() < TOL);
+        assert!((y.inverse().unwrap().matrix() - z.matrix()).norm() < TOL);
+        assert!(sva::ImpedanceMatrix::try_from(sva::ImpedanceVector::zero())
+            .unwrap()
+            .inverse()
+            .is_none());
+        // negative components would break positive semi-definiteness
+        assert_eq!(
+            sva::ImpedanceMatrix::try_from(sva::ImpedanceVector::from_scalars(1., -1.)),
+            Err(sva::NegativeComponentError)
+        );
+        assert_eq!(
+            sva::AdmittanceMatrix::try_from(sva::AdmittanceVector::from_scalars(-1., 1.)),
+            Err(sva::NegativeComponentError)
+        );
+
+        // frame changes
+        let X_a_b = sva::PTransform::from_mat_vec(
+            sva::Quat::from_scaled_axis(sva::Vec3::new_random()).to_rotation_matrix(),
+            sva::Vec3::new_random(),
+        );
+        let z_b = X_a_b.impedance_dual_mul(&z);
+        assert!(
+            (X_a_b.dual_mul(&(z * mv)) - z_b * (X_a_b * mv))
+                .vector()
+                .norm()
+                < TOL
+        );
+        assert!((X_a_b.impedance_trans_mul(&z_b).matrix() - z.matrix()).norm() < TOL);
+        assert!(sva::is_symmetric_positive_definite(&z_b.matrix()));
+
+        let y_b = X_a_b.admittance_mul(&y);
+        assert!(
+            ((X_a_b * (y * fv)) - y_b * X_a_b.dual_mul(&fv))
+                .vector()
+                .norm()
+                < TOL
+        );
+        assert!((X_a_b.admittance_inv_mul(&y_b).matrix() - y.matrix()).norm() < TOL);
+        assert!((y_b.inverse().unwrap().matrix() - z_b.matrix()).norm() < TOL);
+    }
+
+    #[test]
+    fn impedance_admittance_inverse_test() {
+        let z = sva::ImpedanceVector::from_vector(sva::Vec6::new_random() + sva::Vec6::repeat(0.1));
+        let y = z.inverse().unwrap();
+        let fv = sva::ForceVector::from_vector(sva::Vec6::new_random());
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+
+        // Z * (Y * f) == f
+        assert!((z * (y * fv) - fv).vector().norm() < TOL);
+        // Y * (Z * m) == m
+        assert!((y * (z * mv) - mv).vector().norm() < TOL);
+
+        // round trip
+        assert!((y.inverse().unwrap() - z).vector().norm() < TOL);
+
+        // conversions
+        assert_eq!(sva::AdmittanceVector::try_from(z), Ok(y));
+        assert_eq!(sva::ImpedanceVector::try_from(y), Ok(y.inverse().unwrap()));
+
+        // zero components
+        let mut z_zero = z;
+        z_zero.linear[1] = 0.;
+        assert_eq!(z_zero.inverse(), None);
+        assert_eq!(
+            sva::AdmittanceVector::try_from(z_zero),
+            Err(sva::ZeroComponentError)
+        );
+        let mut y_zero = y;
+        y_zero.angular[2] = 0.;
+        assert_eq!(y_zero.inverse(), None);
+        assert_eq!(sva::ImpedanceVector::zero().inverse(), None);
+        assert_eq!(sva::AdmittanceVector::zero().inverse(), None);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn impedance_controller_test() {
+        let stiffness = sva::ImpedanceVector::from_vectors(
+            sva::Vec3::new(10., 20., 30.),
+            sva::Vec3::new(100., 200., 300.),
+        );
+        let damping = sva::ImpedanceVector::from_scalars(2., 20.);
+        let controller = sva::ImpedanceController::new(stiffness, damping);
+        let E_0_c =
+            sva::Quat::from_scaled_axis(sva::Vec3::new(0.3, -0.2, 1.1)).to_rotation_matrix();
+        let X_0_c = sva::PTransform::from_mat_vec(E_0_c, sva::Vec3::new(1., 2., 3.));
+        let zero = sva::MotionVector::zero();
+
+        // no error, no force
+        let f = controller.compute(&X_0_c, &X_0_c, &zero, &zero);
+        assert!(f.vector().norm() < TOL);
+
+        // translation error along the reference x axis
+        let X_0_d = sva::PTransform::from_mat_vec(E_0_c, sva::Vec3::new(1.1, 2., 3.));
+        let f = controller.compute(&X_0_c, &X_0_d, &zero, &zero);
+        assert!((f.couple).norm() < TOL);
+        assert!((f.force - E_0_c * sva::Vec3::new(100. * 0.1, 0., 0.)).norm() < TOL);
+
+        // same error with the gains applied in the current frame
+        let controller_current = controller.with_gain_frame(sva::GainFrame::Current);
+        let f = controller_current.compute(&X_0_c, &X_0_d, &zero, &zero);
+        let error_c = E_0_c * sva::Vec3::new(0.1, 0., 0.);
+        assert!((f.force - stiffness.linear.component_mul(&error_c)).norm() < TOL);
+
+        // both gain frames agree with isotropic gains
+        let iso = sva::ImpedanceController::new(
+            sva::ImpedanceVector::from_scalars(5., 50.),
+            sva::ImpedanceVector::from_scalars(1., 10.),
+        );
+        let X_0_d =
+            sva::PTransform::from_mat_vec(sva::rot_y(0.2) * E_0_c, sva::Vec3::new(0.9, 2.1, 3.2));
+        let v_c = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let v_d = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let f_ref = iso.compute(&X_0_c, &X_0_d, &v_c, &v_d);
+        let f_cur = iso
+            .with_gain_frame(sva::GainFrame::Current)
+            .compute(&X_0_c, &X_0_d, &v_c, &v_d);
+        assert!((f_ref - f_cur).vector().norm() < TOL);
+
+        // a velocity in the current frame gives an opposed damping force
+        let f = controller_current.compute(&X_0_c, &X_0_c, &v_c, &zero);
+        assert!((f + damping * v_c).vector().norm() < TOL);
+
+        // critical damping
+        let inertia = sva::ImpedanceVector::from_scalars(0.5, 2.);
+        let critical = sva::critical_damping(&stiffness, &inertia);
+        assert!((critical.linear[1] - 2. * (200f64 * 2.).sqrt()).abs() < TOL);
+
+        // a critically damped point mass reaches the target without overshoot
+        let mass = 2.;
+        let controller = sva::ImpedanceController::critically_damped(
+            sva::ImpedanceVector::from_scalars(10., 100.),
+            sva::ImpedanceVector::from_scalars(1., mass),
+        );
+        let X_0_d = sva::PTransform::identity();
+        let mut position = sva::Vec3::new(0.5, 0., 0.);
+        let mut velocity = sva::MotionVector::zero();
+        let dt = 1e-3;
+        for _ in 0..5000 {
+            let X_0_c = sva::PTransform::from_vec(position);
+            let f = controller.compute(&X_0_c, &X_0_d, &velocity, &zero);
+            velocity.linear += f.force / mass * dt;
+            position += velocity.linear * dt;
+            assert!(position[0] > -1e-6);
+        }
+        assert!(position.norm() < 1e-3);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn transform_exp_test() {
+        let mv = sva::MotionVector::from_vectors(
+            sva::Vec3::new(0.4, -1.2, 0.7),
+            sva::Vec3::new(1., 0.5, -2.),
+        );
+
+        // composition of many small steps
+        let n = 10000;
+        let step = sva::transform_exp(&(mv / n as f64));
+        let mut X = sva::PTransform::identity();
+        for _ in 0..n {
+            X = step * X;
+        }
+        let X_exp = sva::transform_exp(&mv);
+        assert!((X.matrix() - X_exp.matrix()).norm() < TOL);
+
+        // pure translation and small rotations
+        let v = sva::Vec3::new_random();
+        assert_eq!(
+            sva::transform_exp(&sva::MotionVector::from_vectors(sva::Vec3::zeros(), v)),
+            sva::PTransform::from_vec(v)
+        );
+        let small = sva::MotionVector::from_vectors(sva::Vec3::new(1e-6, 0., 0.), v);
+        let X_small = sva::transform_exp(&small);
+        assert!((sva::transform_velocity(&X_small) - small).vector().norm() < TOL);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn admittance_controller_test() {
+        let mass = sva::ImpedanceVector::from_scalars(0.1, 1.);
+        let damping = sva::ImpedanceVector::from_scalars(2., 40.);
+        let stiffness = sva::ImpedanceVector::from_scalars(5., 100.);
+        let dt = 1e-3;
+
+        assert!(sva::AdmittanceController::from_vectors(
+            sva::ImpedanceVector::zero(),
+            damping,
+            stiffness
+        )
+        .is_none());
+
+        // constant external wrench in free space: e = f / K
+        let mut controller =
+            sva::AdmittanceController::from_vectors(mass, damping, stiffness).unwrap();
+        let f = sva::ForceVector::from_vectors(
+            sva::Vec3::new(0., 0., 0.5),
+            sva::Vec3::new(10., 0., 0.),
+        );
+        for _ in 0..5000 {
+            controller.update(&f, dt);
+        }
+        let deflection = controller.deflection();
+        assert!((deflection.linear - sva::Vec3::new(0.1, 0., 0.)).norm() < 1e-3);
+        assert!((deflection.angular - sva::Vec3::new(0., 0., 0.1)).norm() < 1e-3);
+        assert!((controller.offset().rotation().angle() - 0.1).abs() < 1e-3);
+        assert!(controller.velocity().vector().norm() < 1e-3);
+        assert!((stiffness * controller.deflection() - f).vector().norm() < 1e-2);
+
+        // pushing against a spring environment located at the reference
+        controller.reset();
+        assert_eq!(controller.offset(), sva::PTransform::identity());
+        let k_env = 300.;
+        let X_0_ref = sva::PTransform::from_vec(sva::Vec3::new(0., 0., 1.));
+        for _ in 0..5000 {
+            let X_0_c = controller.pose(&X_0_ref);
+            let env_force = -k_env * (X_0_c.translation()[2] - 1.);
+            let measured = sva::ForceVector::from_vectors(
+                sva::Vec3::zeros(),
+                sva::Vec3::new(0., 0., 20. + env_force),
+            );
+            controller.update(&measured, dt);
+        }
+        let X_0_c = controller.pose(&X_0_ref);
+        assert!((X_0_c.translation()[2] - 1. - 20. / (100. + k_env)).abs() < 1e-4);
+
+        // saturations
+        let max_velocity =
+            sva::MotionVector::from_vectors(sva::Vec3::repeat(0.2), sva::Vec3::repeat(0.05));
+        let max_deflection =
+            sva::MotionVector::from_vectors(sva::Vec3::repeat(0.02), sva::Vec3::repeat(0.03));
+        let mut controller = sva::AdmittanceController::from_vectors(mass, damping, stiffness)
+            .unwrap()
+            .with_max_velocity(max_velocity)
+            .with_max_deflection(max_deflection);
+        for _ in 0..2000 {
+            controller.update(&f, dt);
+            assert!(controller.velocity().linear.amax() <= 0.05 + 1e-12);
+            assert!(controller.velocity().angular.amax() <= 0.2 + 1e-12);
+            let deflection = sva::transform_velocity(&controller.offset());
+            assert!(deflection.linear.amax() <= 0.03 + 1e-9);
+            assert!(deflection.angular.amax() <= 0.02 + 1e-9);
+        }
+        let deflection = sva::transform_velocity(&controller.offset());
+        assert!((deflection.linear[0] - 0.03).abs() < 1e-9);
+        assert!((deflection.angular[2] - 0.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn admittance_controller_inverse_mass_test() {
+        let mass = sva::ImpedanceVector::from_scalars(0.1, 1.);
+        let damping = sva::ImpedanceVector::from_scalars(2., 40.);
+        let stiffness = sva::ImpedanceVector::from_scalars(5., 100.);
+        let dt = 1e-3;
+        let f = sva::ForceVector::from_vectors(
+            sva::Vec3::new(0., 0., 0.5),
+            sva::Vec3::new(10., -3., 0.),
+        );
+
+        // same behavior as with the mass
+        let inverse_mass = sva::AdmittanceVector::try_from(mass).unwrap();
+        let mut controller =
+            sva::AdmittanceController::from_inverse_mass_vectors(inverse_mass, damping, stiffness)
+                .unwrap();
+        let mut expected =
+            sva::AdmittanceController::from_vectors(mass, damping, stiffness).unwrap();
+        for _ in 0..100 {
+            controller.update(&f, dt);
+            expected.update(&f, dt);
+        }
+        assert!(
+            (controller.velocity() - expected.velocity())
+                .vector()
+                .norm()
+                < TOL
+        );
+        assert!(
+            (controller.deflection() - expected.deflection())
+                .vector()
+                .norm()
+                < TOL
+        );
+
+        // zero admittance: the rotations are locked
+        let inverse_mass =
+            sva::AdmittanceVector::from_vectors(sva::Vec3::zeros(), sva::Vec3::repeat(1.));
+        let mut controller =
+            sva::AdmittanceController::from_inverse_mass_vectors(inverse_mass, damping, stiffness)
+                .unwrap();
+        for _ in 0..100 {
+            controller.update(&f, dt);
+        }
+        assert_eq!(controller.velocity().angular, sva::Vec3::zeros());
+        assert_eq!(controller.offset().rotation(), sva::Rot3::identity());
+        assert!(controller.velocity().linear.norm() > 0.);
+
+        assert!(sva::AdmittanceController::from_inverse_mass_vectors(
+            sva::AdmittanceVector::from_scalars(-1., 1.),
+            damping,
+            stiffness
+        )
+        .is_none());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn typed_frames_test() {
+        use sva::typed;
+
+        struct A;
+        struct B;
+        struct C;
+
+        let X_a_b = sva::PTransform::from_mat_vec(sva::rot_x(0.4), sva::Vec3::new_random());
+        let X_b_c = sva::PTransform::from_mat_vec(sva::rot_z(-1.1), sva::Vec3::new_random());
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let fv = sva::ForceVector::from_vector(sva::Vec6::new_random());
+
+        let tX_a_b = typed::PTransform::<A, B>::from_untyped(X_a_b);
+        let tX_b_c = typed::PTransform::<B, C>::from_untyped(X_b_c);
+        let mv_a = typed::MotionVector::<A>::from_untyped(mv);
+        let fv_a = typed::ForceVector::<A>::from_untyped(fv);
+
+        let mv_b: typed::MotionVector<B> = tX_a_b * mv_a;
+        assert_eq!(mv_b.untyped(), X_a_b * mv);
+        let fv_b: typed::ForceVector<B> = tX_a_b.dual_mul(&fv_a);
+        assert_eq!(fv_b.untyped(), X_a_b.dual_mul(&fv));
+        let mv_a2: typed::MotionVector<A> = tX_a_b.inv_mul(&mv_b);
+        assert_eq!(mv_a2.untyped(), X_a_b.inv_mul(&(X_a_b * mv)));
+        let fv_a2: typed::ForceVector<A> = tX_a_b.trans_mul(&fv_b);
+        assert_eq!(fv_a2.untyped(), X_a_b.trans_mul(&X_a_b.dual_mul(&fv)));
+
+        let tX_a_c: typed::PTransform<A, C> = tX_b_c * tX_a_b;
+        assert_eq!(tX_a_c.untyped(), X_b_c * X_a_b);
+        let tX_c_a: typed::PTransform<C, A> = tX_a_c.inv();
+        assert!(
+            ((tX_c_a * (tX_a_c * mv_a)) - mv_a)
+                .untyped()
+                .vector()
+                .norm()
+                < TOL
+        );
+        assert_eq!(
+            (typed::PTransform::<B, B>::identity() * mv_b).untyped(),
+            mv_b.untyped()
+        );
+
+        // same frame algebra
+        assert_eq!((mv_a + mv_a2).untyped(), mv + mv_a2.untyped());
+        assert_eq!(mv_a.cross(mv_a).untyped(), mv.cross(mv));
+        assert_eq!(mv_a.cross_dual(fv_a).untyped(), mv.cross_dual(fv));
+        assert_eq!(mv_a.dot(fv_a), mv.dot(fv));
+        assert_eq!((2. * fv_a - fv_a).untyped(), 2. * fv - fv);
+        assert_eq!(
+            std::mem::size_of::<typed::PTransform<A, B>>(),
+            std::mem::size_of::<sva::PTransform>()
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn interpolate_test() {
+        let from = sva::PTransform::from_mat_vec(sva::rot_x(0.2), sva::Vec3::new(1., 2., 3.));
+        let to = sva::PTransform::from_mat_vec(sva::rot_x(0.6), sva::Vec3::new(3., 2., 1.));
+
+        assert!((sva::interpolate(&from, &to, 0.).matrix() - from.matrix()).norm() < TOL);
+        assert!((sva::interpolate(&from, &to, 1.).matrix() - to.matrix()).norm() < TOL);
+        let mid = sva::PTransform::from_mat_vec(sva::rot_x(0.4), sva::Vec3::new(2., 2., 2.));
+        assert!((sva::interpolate(&from, &to, 0.5).matrix() - mid.matrix()).norm() < TOL);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn frame_tree_test() {
+        let mut tree = sva::FrameTree::new();
+        tree.add_root("world").unwrap();
+        tree.add_frame("base", "world").unwrap();
+        tree.add_frame("hand", "base").unwrap();
+        tree.add_frame("camera", "world").unwrap();
+        tree.add_root("other").unwrap();
+
+        let X_w_b0 = sva::PTransform::from_vec(sva::Vec3::new(0., 0., 0.));
+        let X_w_b1 = sva::PTransform::from_vec(sva::Vec3::new(1., 0., 0.));
+        let X_b_h = sva::PTransform::from_mat_vec(sva::rot_z(0.3), sva::Vec3::new(0., 0.5, 1.));
+        let X_w_c = sva::PTransform::from_mat_vec(sva::rot_y(-0.8), sva::Vec3::new(2., 1., 0.));
+        tree.set_transform("base", 0., X_w_b0).unwrap();
+        tree.set_transform("base", 1., X_w_b1).unwrap();
+        tree.set_static_transform("hand", X_b_h).unwrap();
+        tree.set_static_transform("camera", X_w_c).unwrap();
+
+        // queries at samples
+        let X_w_h = tree.transform("world", "hand", 1.).unwrap();
+        assert!((X_w_h.matrix() - (X_b_h * X_w_b1).matrix()).norm() < TOL);
+        let X_h_w = tree.transform("hand", "world", 1.).unwrap();
+        assert!((X_h_w.matrix() - X_w_h.inv().matrix()).norm() < TOL);
+
+        // queries between samples use interpolation, across branches
+        let X_w_bt = sva::PTransform::from_vec(sva::Vec3::new(0.25, 0., 0.));
+        let X_c_h = tree.transform("camera", "hand", 0.25).unwrap();
+        assert!((X_c_h.matrix() - (X_b_h * X_w_bt * X_w_c.inv()).matrix()).norm() < TOL);
+
+        // re-expressing spatial vectors
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let fv = sva::ForceVector::from_vector(sva::Vec6::new_random());
+        assert_eq!(
+            tree.transform_motion("camera", "hand", 0.25, &mv).unwrap(),
+            X_c_h * mv
+        );
+        assert_eq!(
+            tree.transform_force("camera", "hand", 0.25, &fv).unwrap(),
+            X_c_h.dual_mul(&fv)
+        );
+        assert_eq!(
+            tree.transform("hand", "hand", 5.).unwrap(),
+            sva::PTransform::identity()
+        );
+
+        // errors
+        assert_eq!(
+            tree.transform("world", "nowhere", 0.).unwrap_err(),
+            sva::FrameTreeError::UnknownFrame("nowhere".to_string())
+        );
+        assert_eq!(
+            tree.add_frame("hand", "world").unwrap_err(),
+            sva::FrameTreeError::DuplicateFrame("hand".to_string())
+        );
+        assert_eq!(
+            tree.set_parent("base", "hand").unwrap_err(),
+            sva::FrameTreeError::Cycle {
+                frame: "base".to_string(),
+                parent: "hand".to_string()
+            }
+        );
+        assert!(tree.set_parent("base", "base").is_err());
+        assert_eq!(
+            tree.transform("hand", "other", 0.).unwrap_err(),
+            sva::FrameTreeError::Disconnected {
+                from: "hand".to_string(),
+                to: "other".to_string()
+            }
+        );
+        assert_eq!(
+            tree.transform("camera", "hand", 1.5).unwrap_err(),
+            sva::FrameTreeError::OutOfRange {
+                frame: "base".to_string(),
+                time: 1.5
+            }
+        );
+        assert!(tree.transform("camera", "hand", -0.1).is_err());
+
+        // re-parenting
+        tree.set_parent("camera", "hand").unwrap();
+        assert_eq!(tree.parent("camera").unwrap(), Some("hand"));
+        assert!(tree.transform("camera", "hand", 0.).is_err());
+
+        // buffer duration
+        let mut tree = sva::FrameTree::new().with_buffer_duration(1.);
+        tree.add_root("world").unwrap();
+        tree.add_frame("base", "world").unwrap();
+        for i in 0..10 {
+            tree.set_transform("base", i as f64 * 0.5, X_w_b0).unwrap();
+        }
+        assert!(tree.transform("world", "base", 3.5).is_ok());
+        assert!(tree.transform("world", "base", 4.2).is_ok());
+        assert!(tree.transform("world", "base", 3.4).is_err());
+    }
+
+    fn random_rb_inertia() -> sva::RBInertia {
+        let mass = 1. + sva::Vec3::new_random()[0] * 10.;
+        let m = sva::Mat3::new_random();
+        sva::RBInertia::from_com_inertia(
+            mass,
+            sva::Vec3::new_random(),
+            m * m.transpose() + sva::Mat3::identity(),
+        )
+    }
+
+    fn random_transform() -> sva::PTransform {
+        sva::PTransform::from_quat_vec(
+            sva::Quat::from_scaled_axis(sva::Vec3::new_random() * 2.),
+            sva::Vec3::new_random(),
+        )
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn rb_inertia_test() {
+        let rbi = random_rb_inertia();
+        let rbi2 = random_rb_inertia();
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+
+        // operators
+        assert!(((rbi * mv).vector() - rbi.matrix() * mv.vector()).norm() < TOL);
+        assert!(((rbi + rbi2).matrix() - (rbi.matrix() + rbi2.matrix())).norm() < TOL);
+        assert!(((2. * rbi).matrix() - 2. * rbi.matrix()).norm() < TOL);
+
+        // com inertia round trip
+        let rbi_com = sva::RBInertia::from_com_inertia(rbi.mass, rbi.com(), rbi.com_inertia());
+        assert!((rbi_com.matrix() - rbi.matrix()).norm() < TOL);
+
+        // frame changes
+        let X_a_b = random_transform();
+        let rbi_b = X_a_b.inertia_dual_mul(&rbi);
+        let expected = X_a_b.dual_matrix() * rbi.matrix() * X_a_b.inv().matrix();
+        assert!((rbi_b.matrix() - expected).norm() < TOL);
+        assert!((X_a_b.inertia_trans_mul(&rbi_b).matrix() - rbi.matrix()).norm() < TOL);
+        assert!(
+            (X_a_b.dual_mul(&(rbi * mv)) - rbi_b * (X_a_b * mv))
+                .vector()
+                .norm()
+                < TOL
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn centroidal_test() {
+        // three free-floating bodies, q_dot is the stack of the body velocities
+        let nr_bodies = 3;
+        let inertias: Vec<sva::RBInertia> = (0..nr_bodies).map(|_| random_rb_inertia()).collect();
+        let poses: Vec<sva::PTransform> = (0..nr_bodies).map(|_| random_transform()).collect();
+        let velocities: Vec<sva::MotionVector> = (0..nr_bodies)
+            .map(|_| sva::MotionVector::from_vector(sva::Vec6::new_random()))
+            .collect();
+        let jacobians: Vec<sva::Mat6X> = (0..nr_bodies)
+            .map(|i| {
+                let mut jac = sva::Mat6X::zeros(6 * nr_bodies);
+                jac.fixed_columns_mut::<nalgebra::U6>(6 * i)
+                    .copy_from(&sva::Mat6::identity());
+                jac
+            })
+            .collect();
+        let mut q_dot = sva::VecX::zeros(6 * nr_bodies);
+        for (i, velocity) in velocities.iter().enumerate() {
+            q_dot
+                .fixed_rows_mut::<nalgebra::U6>(6 * i)
+                .copy_from(&velocity.vector());
+        }
+        let total_mass = sva::total_mass(&inertias);
+
+        // center of mass
+        let com = sva::center_of_mass(&inertias, &poses);
+        let mut expected_com = sva::Vec3::zeros();
+        for (inertia, X_0_i) in inertias.iter().zip(&poses) {
+            expected_com +=
+                inertia.mass * (X_0_i.translation() + X_0_i.rotation().transpose() * inertia.com());
+        }
+        assert!((com - expected_com / total_mass).norm() < TOL);
+
+        // momentum and centroidal momentum matrix
+        let h_G = sva::centroidal_momentum(&inertias, &poses, &velocities);
+        let cmm = sva::centroidal_momentum_matrix(&inertias, &poses, &jacobians);
+        assert!((&cmm * &q_dot - h_G.vector()).norm() < TOL);
+
+        // com velocity
+        let com_jac = sva::com_jacobian(&inertias, &poses, &jacobians);
+        assert!((&com_jac * &q_dot - h_G.force / total_mass).norm() < TOL);
+
+        // momentum derivative with constant body velocities: h_G_dot = A_dot * q_dot
+        let zeros = vec![sva::MotionVector::zero(); nr_bodies];
+        let bias = sva::centroidal_momentum_bias(&inertias, &poses, &velocities, &zeros);
+        let h = 1e-5;
+        let momentum_at = |t: f64| {
+            let poses_t: Vec<sva::PTransform> = poses
+                .iter()
+                .zip(&velocities)
+                .map(|(X_0_i, v)| sva::transform_exp(&(*v * t)) * *X_0_i)
+                .collect();
+            sva::centroidal_momentum(&inertias, &poses_t, &velocities)
+        };
+        let h_G_dot = (momentum_at(h) - momentum_at(-h)) / (2. * h);
+        assert!((h_G_dot - bias).vector().norm() < 1e-4);
+
+        // the bias acceleration contribution
+        let bias_accelerations: Vec<sva::MotionVector> = (0..nr_bodies)
+            .map(|_| sva::MotionVector::from_vector(sva::Vec6::new_random()))
+            .collect();
+        let mut bias_acc_vec = sva::VecX::zeros(6 * nr_bodies);
+        for (i, acc) in bias_accelerations.iter().enumerate() {
+            bias_acc_vec
+                .fixed_rows_mut::<nalgebra::U6>(6 * i)
+                .copy_from(&acc.vector());
+        }
+        let bias_with_acc =
+            sva::centroidal_momentum_bias(&inertias, &poses, &velocities, &bias_accelerations);
+        assert!(((bias_with_acc - bias).vector() - cmm * bias_acc_vec).norm() < TOL);
+    }
+
+    #[test]
+    fn nnls_test() {
+        let a = sva::MatX::from_row_slice(3, 2, &[1., 0., 0., 1., 1., 1.]);
+
+        // unconstrained solution is feasible
+        let b = sva::VecX::from_row_slice(3, &[1., 2., 3.]);
+        let x = sva::nnls(&a, &b);
+        assert!((x - sva::VecX::from_row_slice(2, &[1., 2.])).norm() < TOL);
+
+        // unconstrained solution has a negative component
+        let b = sva::VecX::from_row_slice(3, &[-1., 2., 1.]);
+        let x = sva::nnls(&a, &b);
+        assert!(x[0].abs() < TOL);
+        assert!((x[1] - 1.5).abs() < TOL);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn contact_wrench_cone_test() {
+        let mu = 0.7;
+        let cone = sva::linearized_friction_cone(&sva::Vec3::z(), mu, 4);
+        assert_eq!(cone.len(), 4);
+        for generator in &cone {
+            let tangential = (generator[0].powi(2) + generator[1].powi(2)).sqrt();
+            assert!((tangential / generator[2] - mu).abs() < TOL);
+        }
+
+        // flat foot with four corners, the cone is expressed at the ankle
+        let corners = [
+            sva::Vec3::new(0.1, 0.05, 0.),
+            sva::Vec3::new(-0.1, 0.05, 0.),
+            sva::Vec3::new(-0.1, -0.05, 0.),
+            sva::Vec3::new(0.1, -0.05, 0.),
+        ];
+        let X_sole_ankle = sva::PTransform::from_vec(sva::Vec3::new(0., 0., 0.1));
+        let cwc =
+            sva::ContactWrenchCone::from_polygon(&corners, &sva::Vec3::z(), mu, 4, &X_sole_ankle);
+        assert_eq!(cwc.generators().len(), 16);
+
+        // wrench at the ankle of a force applied at a point of the sole
+        let at_ankle = |point: sva::Vec3, force: sva::Vec3| {
+            X_sole_ankle.dual_mul(&sva::ForceVector::from_vectors(point.cross(&force), force))
+        };
+        let center = sva::Vec3::zeros();
+
+        // vertical force at the center
+        let w = at_ankle(center, sva::Vec3::new(0., 0., 100.));
+        assert!(cwc.contains(&w));
+        let weights = cwc.decompose(&w).unwrap();
+        assert!(weights.iter().all(|&x| x >= 0.));
+        assert!((cwc.generators_matrix() * weights - w.vector()).norm() < 1e-6);
+
+        // friction limits
+        assert!(cwc.contains(&at_ankle(center, sva::Vec3::new(50., 0., 100.))));
+        assert!(!cwc.contains(&at_ankle(center, sva::Vec3::new(80., 0., 100.))));
+        assert!(!cwc.contains(&at_ankle(center, sva::Vec3::new(0., 0., -100.))));
+
+        // center of pressure inside and outside of the foot
+        let inside = sva::Vec3::new(0.08, -0.03, 0.);
+        let outside = sva::Vec3::new(0., 0.1, 0.);
+        assert!(cwc.contains(&at_ankle(inside, sva::Vec3::new(0., 10., 100.))));
+        assert!(!cwc.contains(&at_ankle(outside, sva::Vec3::new(0., 0., 100.))));
+
+        // small yaw torque
+        let yaw = sva::ForceVector::from_vectors(sva::Vec3::new(0., 0., 1.), sva::Vec3::zeros());
+        assert!(cwc.contains(&(at_ankle(center, sva::Vec3::new(0., 0., 100.)) + yaw)));
+        assert!(!cwc.contains(&(at_ankle(center, sva::Vec3::new(0., 0., 100.)) + 100. * yaw)));
+
+        // changing the frame of the cone
+        let X_ankle_other =
+            sva::PTransform::from_mat_vec(sva::rot_z(0.5), sva::Vec3::new(0.3, 0., 0.));
+        let cwc_other = cwc.transform(&X_ankle_other);
+        let w = at_ankle(inside, sva::Vec3::new(0., 10., 100.));
+        assert!(cwc_other.contains(&X_ankle_other.dual_mul(&w)));
+        let w = at_ankle(outside, sva::Vec3::new(0., 0., 100.));
+        assert!(!cwc_other.contains(&X_ankle_other.dual_mul(&w)));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn center_of_pressure_test() {
+        let point_force = |point: sva::Vec3, force: sva::Vec3| {
+            sva::ForceVector::from_vectors(point.cross(&force), force)
+        };
+
+        // point force on the ground with a free couple along the normal
+        let ground = sva::PTransform::identity();
+        let point = sva::Vec3::new(0.3, -0.2, 0.);
+        let mut wrench = point_force(point, sva::Vec3::new(5., -3., 50.));
+        wrench.couple[2] += 2.;
+        let cop = sva::center_of_pressure(&wrench, &ground, 1.).unwrap();
+        assert!((cop - point).norm() < TOL);
+
+        // force applied above the plane, the cop is along the line of action
+        let above = sva::Vec3::new(0.1, 0.2, 0.5);
+        let force = sva::Vec3::new(10., 0., 50.);
+        let cop = sva::center_of_pressure(&point_force(above, force), &ground, 1.).unwrap();
+        assert!((cop - (above - force * 0.5 / 50.)).norm() < TOL);
+
+        // tilted plane, measured in a sensor frame
+        let X_0_p = sva::PTransform::from_mat_vec(sva::rot_x(0.3), sva::Vec3::new(0., 0., 0.2));
+        let X_0_s = sva::PTransform::from_mat_vec(sva::rot_y(-0.4), sva::Vec3::new(0.1, 0., 0.5));
+        let point_p = sva::Vec3::new(0.05, 0.1, 0.);
+        let normal_0 = X_0_p.rotation().transpose() * sva::Vec3::z();
+        let point_0 = X_0_p.translation() + X_0_p.rotation().transpose() * point_p;
+        let wrench_0 = point_force(point_0, 40. * normal_0 + sva::Vec3::new(1., 2., 0.));
+        let wrench_s = X_0_s.dual_mul(&wrench_0);
+        let X_s_p = X_0_p * X_0_s.inv();
+        let cop_s = sva::center_of_pressure(&wrench_s, &X_s_p, 1.).unwrap();
+        assert!(
+            (X_0_s.translation() + X_0_s.rotation().transpose() * cop_s - point_0).norm() < TOL
+        );
+
+        // too small or pulling normal forces
+        let weak = point_force(point, sva::Vec3::new(0., 0., 0.5));
+        assert_eq!(sva::center_of_pressure(&weak, &ground, 1.), None);
+        assert_eq!(sva::center_of_pressure(&-wrench, &ground, 1.), None);
+
+        // zero moment point of two feet standing on the ground
+        let X_0_left =
+            sva::PTransform::from_mat_vec(sva::rot_z(0.2), sva::Vec3::new(0., 0.1, 0.05));
+        let X_0_right = sva::PTransform::from_vec(sva::Vec3::new(0.1, -0.1, 0.05));
+        let cop_left = sva::Vec3::new(0.02, 0.1, 0.);
+        let cop_right = sva::Vec3::new(0.12, -0.08, 0.);
+        let left_0 = point_force(cop_left, sva::Vec3::new(0., 0., 300.));
+        let right_0 = point_force(cop_right, sva::Vec3::new(0., 0., 100.));
+        let wrenches = [X_0_left.dual_mul(&left_0), X_0_right.dual_mul(&right_0)];
+        let poses = [X_0_left, X_0_right];
+        let zmp = sva::zero_moment_point(&wrenches, &poses, &ground, 1.).unwrap();
+        assert!((zmp - (300. * cop_left + 100. * cop_right) / 400.).norm() < TOL);
+        assert_eq!(
+            sva::zero_moment_point(&wrenches, &poses, &ground, 1000.),
+            None
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn payload_test() {
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+        let payload = sva::Payload::new(
+            1.5,
+            sva::Vec3::new(0.01, -0.02, 0.08),
+            sva::ForceVector::from_vectors(
+                sva::Vec3::new(0.1, -0.3, 0.05),
+                sva::Vec3::new(2., -1., 4.),
+            ),
+        );
+        let measure = |E_0_s: &sva::Rot3| payload.offset + payload.gravity_wrench(E_0_s, &gravity);
+
+        let orientations: Vec<sva::Rot3> = (0..10)
+            .map(|i| {
+                let i = i as f64;
+                sva::rot_x(0.7 * i) * sva::rot_y(1.3 * i) * sva::rot_z(-0.4 * i)
+            })
+            .collect();
+
+        // noise free identification
+        let samples: Vec<(sva::Rot3, sva::ForceVector)> =
+            orientations.iter().map(|E| (*E, measure(E))).collect();
+        let identified = sva::Payload::identify(&samples, &gravity).unwrap();
+        assert!((identified.mass - payload.mass).abs() < TOL);
+        assert!((identified.com - payload.com).norm() < TOL);
+        assert!((identified.offset - payload.offset).vector().norm() < TOL);
+
+        // noisy identification
+        let noisy_samples: Vec<(sva::Rot3, sva::ForceVector)> = orientations
+            .iter()
+            .enumerate()
+            .map(|(i, E)| {
+                let noise = sva::Vec6::from_fn(|r, _| 0.01 * ((7 * i + 3 * r) as f64).sin());
+                (*E, measure(E) + sva::ForceVector::from_vector(noise))
+            })
+            .collect();
+        let identified = sva::Payload::identify(&noisy_samples, &gravity).unwrap();
+        assert!((identified.mass - payload.mass).abs() < 1e-2);
+        assert!((identified.com - payload.com).norm() < 1e-2);
+
+        // compensation at a new orientation
+        let E_0_s = sva::rot_x(-2.1) * sva::rot_z(0.3);
+        let external = sva::ForceVector::from_vectors(
+            sva::Vec3::new(0.5, 0., -0.2),
+            sva::Vec3::new(3., 4., 5.),
+        );
+        let measured = measure(&E_0_s) + external;
+        let compensated = identified.compensate(&E_0_s, &measured, &gravity);
+        assert!((compensated - external).vector().norm() < 5e-2);
+
+        // a single orientation is not enough
+        let single = vec![samples[0]; 5];
+        assert_eq!(sva::Payload::identify(&single, &gravity), None);
+
+        // no payload, with a slightly negative mass due to the noise
+        let unloaded = sva::Payload::new(-1e-4, sva::Vec3::zeros(), payload.offset);
+        for payload in &[
+            sva::Payload::new(0., sva::Vec3::zeros(), payload.offset),
+            unloaded,
+        ] {
+            let samples: Vec<(sva::Rot3, sva::ForceVector)> = orientations
+                .iter()
+                .map(|E| (*E, payload.offset + payload.gravity_wrench(E, &gravity)))
+                .collect();
+            let identified = sva::Payload::identify(&samples, &gravity).unwrap();
+            assert_eq!(identified.mass, 0.);
+            assert_eq!(identified.com, sva::Vec3::zeros());
+            assert!((identified.offset - payload.offset).vector().norm() < TOL);
+        }
+    }
+
+    // Tree mixing all the joint types:
+    // 0 (free) -> 1 (revolute) -> 2 (spherical) -> 4 (revolute)
+    //                          -> 3 (prismatic)
+    fn random_multibody() -> sva::MultiBody {
+        let joints = vec![
+            sva::JointType::Free,
+            sva::JointType::Revolute(sva::Vec3::new(1., 2., 3.).normalize()),
+            sva::JointType::Spherical,
+            sva::JointType::Prismatic(sva::Vec3::new(-1., 0.5, 0.).normalize()),
+            sva::JointType::Revolute(sva::Vec3::z()),
+        ];
+        let parents = vec![None, Some(0), Some(1), Some(1), Some(2)];
+        sva::MultiBody::new(
+            (0..5).map(|_| random_rb_inertia()).collect(),
+            joints,
+            parents,
+            (0..5).map(|_| random_transform()).collect(),
+        )
+    }
+
+    fn random_configuration(mb: &sva::MultiBody) -> sva::VecX {
+        let mut q = sva::VecX::new_random(mb.nr_params());
+        for (i, joint) in mb.joints.iter().enumerate() {
+            if let sva::JointType::Spherical | sva::JointType::Free = joint {
+                let offset = mb.params_offset(i);
+                let quat = sva::params_to_quat(&q.as_slice()[offset..offset + 4]);
+                q.rows_mut(offset, 4)
+                    .copy_from_slice(&sva::quat_to_params(&quat));
+            }
+        }
+        q
+    }
+
+    #[test]
+    fn multibody_test() {
+        let mb = random_multibody();
+        let q = random_configuration(&mb);
+        let alpha = sva::VecX::new_random(mb.nr_dof());
+        let alpha_dot = sva::VecX::new_random(mb.nr_dof());
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+
+        // zero configuration puts every joint at the identity
+        let zero_poses = sva::forward_kinematics(&mb, &mb.zero_params());
+        assert!(zero_poses[0] == mb.transforms[0]);
+
+        let velocities = sva::forward_velocity(&mb, &q, &alpha);
+        for (body, velocity) in velocities.iter().enumerate() {
+            let jac = sva::body_jacobian(&mb, &q, body);
+            assert!((jac * &alpha - velocity.vector()).norm() < TOL);
+        }
+
+        // finite differences of the integrated configuration
+        let dt = 1e-7;
+        let poses = sva::forward_kinematics(&mb, &q);
+        let next_poses = sva::forward_kinematics(&mb, &mb.integrate(&q, &alpha, dt));
+        for ((pose, next_pose), velocity) in poses.iter().zip(&next_poses).zip(&velocities) {
+            let diff = sva::transform_velocity(&(*next_pose * pose.inv())) / dt;
+            assert!((diff - *velocity).vector().norm() < 1e-4);
+        }
+
+        let mass_matrix = sva::mass_matrix(&mb, &q);
+        assert!((&mass_matrix - mass_matrix.transpose()).norm() < TOL);
+
+        let torques = sva::inverse_dynamics(&mb, &q, &alpha, &alpha_dot, &gravity);
+        let acc = sva::forward_dynamics(&mb, &q, &alpha, &torques, &gravity);
+        assert!((acc - alpha_dot).norm() < TOL);
+    }
+
+    #[test]
+    fn regressor_test() {
+        let rbi = random_rb_inertia();
+        let params = rbi.parameters();
+        let rbi_back = sva::RBInertia::from_parameters(&params);
+        assert!((rbi_back.matrix() - rbi.matrix()).norm() < TOL);
+
+        let v = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let a = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let wrench = rbi * a + v.cross_dual(rbi * v);
+        let y = sva::inertial_regressor(&v, &a);
+        assert!((y * params - wrench.vector()).norm() < TOL);
+
+        let mb = random_multibody();
+        let q = random_configuration(&mb);
+        let alpha = sva::VecX::new_random(mb.nr_dof());
+        let alpha_dot = sva::VecX::new_random(mb.nr_dof());
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+        let torques = sva::inverse_dynamics(&mb, &q, &alpha, &alpha_dot, &gravity);
+        let y_tau = sva::joint_torque_regressor(&mb, &q, &alpha, &alpha_dot, &gravity);
+        assert_eq!(y_tau.shape(), (mb.nr_dof(), 10 * mb.nr_bodies()));
+        assert!((y_tau * sva::parameters_vector(&mb.bodies) - torques).norm() < TOL);
+
+        // box of sides 1, 2 and 3 away from its com
+        let box_inertia = sva::Mat3::from_diagonal(&sva::Vec3::new(13., 10., 5.)) / 12.;
+        let consistent =
+            sva::RBInertia::from_com_inertia(2., sva::Vec3::new(0.1, 0.2, 0.3), box_inertia);
+        assert!(consistent.is_physically_consistent());
+        let negative_mass = sva::RBInertia::from_com_inertia(-2., sva::Vec3::zeros(), box_inertia);
+        assert!(!negative_mass.is_physically_consistent());
+        let triangle = sva::Mat3::from_diagonal(&sva::Vec3::new(1., 1., 3.));
+        let not_triangle = sva::RBInertia::from_com_inertia(2., sva::Vec3::zeros(), triangle);
+        assert!(!not_triangle.is_physically_consistent());
+    }
+
+    #[test]
+    fn inverse_kinematics_test() {
+        let mb = random_multibody();
+        let q_target = random_configuration(&mb);
+        let target_poses = sva::forward_kinematics(&mb, &q_target);
+        let perturbation = sva::VecX::from_fn(mb.nr_dof(), |r, _| 0.2 * (r as f64).sin());
+        let q_init = mb.integrate(&q_target, &perturbation, 1.);
+
+        // full pose of a leaf body
+        let ik = sva::InverseKinematics::new(1e-3).with_task(sva::IkTask::new(4, target_poses[4]));
+        let result = ik.solve(&mb, &q_init);
+        assert!(result.converged());
+        assert!(result.iterations > 0);
+        let pose = sva::forward_kinematics(&mb, &result.q)[4];
+        assert!(
+            sva::transform_error(&pose, &target_poses[4])
+                .vector()
+                .norm()
+                < 1e-6
+        );
+
+        // position only
+        let ik = sva::InverseKinematics::new(1e-3).with_task(
+            sva::IkTask::new(3, target_poses[3])
+                .with_weight(sva::ImpedanceVector::from_scalars(0., 1.)),
+        );
+        let result = ik.solve(&mb, &q_init);
+        assert!(result.converged());
+        let pose = sva::forward_kinematics(&mb, &result.q)[3];
+        assert!((pose.translation() - target_poses[3].translation()).norm() < 1e-6);
+
+        // an unreachable secondary task does not disturb the primary one
+        let far = sva::PTransform::from_vec(sva::Vec3::new(100., 0., 0.)) * target_poses[3];
+        let ik = sva::InverseKinematics::new(1e-2)
+            .with_task(sva::IkTask::new(0, target_poses[0]))
+            .with_task(sva::IkTask::new(3, far).with_priority(1))
+            .with_max_error(0.5)
+            .with_max_iterations(50);
+        let result = ik.solve(&mb, &q_init);
+        assert!(!result.converged());
+        assert!(result.errors[0] < 1e-6);
+        assert!(result.errors[1] > 1.);
+
+        // planar arm whose first joint is stopped by its limit
+        let link = sva::PTransform::from_vec(sva::Vec3::x());
+        let arm = sva::MultiBody::new(
+            vec![random_rb_inertia(); 2],
+            vec![sva::JointType::Revolute(sva::Vec3::z()); 2],
+            vec![None, Some(0)],
+            vec![sva::PTransform::identity(), link],
+        );
+        let target =
+            sva::forward_kinematics(&arm, &sva::VecX::from_column_slice(2, &[0.5, 0.5]))[1];
+        let ik = sva::InverseKinematics::new(1e-3)
+            .with_task(sva::IkTask::new(1, target))
+            .with_joint_limit(&arm, 0, -0.2, 0.2);
+        let result = ik.solve(&arm, &sva::VecX::zeros(2));
+        assert!(!result.converged());
+        assert!((result.q[0] - 0.2).abs() < f64::EPSILON);
+        let unlimited = sva::InverseKinematics::new(1e-3).with_task(sva::IkTask::new(1, target));
+        assert!(unlimited.solve(&arm, &sva::VecX::zeros(2)).converged());
+    }
+
+    #[test]
+    #[should_panic(expected = "joint limits only apply to revolute and prismatic joints")]
+    fn ik_spherical_joint_limit_test() {
+        // joint 2 is spherical
+        let mb = random_multibody();
+        let _ = sva::InverseKinematics::new(1e-3).with_joint_limit(&mb, 2, -0.2, 0.2);
+    }
+
+    #[test]
+    fn operational_space_test() {
+        let mb = random_multibody();
+        let q = random_configuration(&mb);
+        let dof = mb.nr_dof();
+        let h = sva::mass_matrix(&mb, &q);
+        let body_jac = sva::body_jacobian(&mb, &q, 4);
+        let jac = sva::MatX::from_fn(6, dof, |r, c| body_jac[(r, c)]);
+
+        let h_inv = h.clone().try_inverse().unwrap();
+        let lambda = sva::operational_space_inertia(&h, &jac).unwrap();
+        assert!(
+            (&lambda * &jac * &h_inv * jac.transpose() - sva::MatX::identity(6, 6)).norm() < TOL
+        );
+
+        let jac_bar = sva::dynamically_consistent_inverse(&h, &jac).unwrap();
+        assert!((&jac * &jac_bar - sva::MatX::identity(6, 6)).norm() < TOL);
+
+        let n = sva::dynamically_consistent_null_space(&h, &jac).unwrap();
+        assert!((&n * &n - &n).norm() < TOL);
+        assert!((&jac * &n).norm() < TOL);
+        assert!((&n * &jac_bar).norm() < TOL);
+        // null space torques produce no task acceleration
+        assert!((&jac * &h_inv * n.transpose()).norm() < TOL);
+        assert!((&n * &h_inv - &h_inv * n.transpose()).norm() < TOL);
+
+        // damping vanishes for small values
+        let lambda_damped = sva::operational_space_inertia_damped(&h, &jac, 1e-6).unwrap();
+        assert!((&lambda_damped - &lambda).norm() < 1e-6 * lambda.norm());
+
+        // duplicated task rows make the Jacobian rank deficient
+        let mut singular = sva::MatX::zeros(7, dof);
+        singular.rows_mut(0, 6).copy_from(&jac);
+        singular.row_mut(6).copy_from(&jac.row(0));
+        assert!(sva::operational_space_inertia(&h, &singular).is_none());
+        let damped = sva::dynamically_consistent_null_space_damped(&h, &singular, 1e-2).unwrap();
+        assert!(damped.iter().all(|x| x.is_finite()));
+        assert!((&singular * &damped).norm() < 1e-2 * singular.norm());
+        // even a small damping regularizes the singular Jacobian
+        let lambda_damped = sva::operational_space_inertia_damped(&h, &singular, 1e-7).unwrap();
+        assert!(lambda_damped.iter().all(|x| x.is_finite()));
+        let jac_bar = sva::dynamically_consistent_inverse_damped(&h, &singular, 1e-7).unwrap();
+        assert!((&singular * &jac_bar * &singular - &singular).norm() < 1e-2 * singular.norm());
+        assert!(sva::dynamically_consistent_inverse(&-h, &jac).is_none());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn transform_integration_test() {
+        let X_0_b = random_transform();
+        let duration = 1.;
+        let velocity = |t: f64| {
+            sva::MotionVector::from_vector(sva::Vec6::new(
+                t.sin(),
+                (2. * t).cos(),
+                0.5,
+                t,
+                1.,
+                -t * t,
+            ))
+        };
+        let acceleration = |t: f64| {
+            sva::MotionVector::from_vector(sva::Vec6::new(
+                t.cos(),
+                -2. * (2. * t).sin(),
+                0.,
+                1.,
+                0.,
+                -2. * t,
+            ))
+        };
+        let field = |t: f64, _: &sva::PTransform| velocity(t);
+
+        // world and body velocities describe the same motion
+        let v_0 = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let from_world = sva::integrate_transform(&X_0_b, &v_0, sva::VelocityFrame::World, 0.3);
+        let from_body =
+            sva::integrate_transform(&X_0_b, &(X_0_b * v_0), sva::VelocityFrame::Body, 0.3);
+        assert!((from_world.matrix() - from_body.matrix()).norm() < TOL);
+
+        let reference = (0..1000).fold(X_0_b, |X, k| {
+            let dt = duration / 1000.;
+            sva::transform_munthe_kaas_rk4_step(
+                &X,
+                k as f64 * dt,
+                dt,
+                sva::VelocityFrame::Body,
+                field,
+            )
+        });
+        let error = |X: &sva::PTransform| sva::transform_error(X, &reference).vector().norm();
+
+        let euler = |steps: usize, semi_implicit: bool| {
+            let dt = duration / steps as f64;
+            let (X, _) = (0..steps).fold((X_0_b, velocity(0.)), |(X, v), k| {
+                let a = acceleration(k as f64 * dt);
+                if semi_implicit {
+                    sva::transform_semi_implicit_euler_step(
+                        &X,
+                        &v,
+                        &a,
+                        sva::VelocityFrame::Body,
+                        dt,
+                    )
+                } else {
+                    sva::transform_euler_step(&X, &v, &a, sva::VelocityFrame::Body, dt)
+                }
+            });
+            error(&X)
+        };
+        for semi_implicit in &[false, true] {
+            let ratio = euler(100, *semi_implicit) / euler(200, *semi_implicit);
+            assert!((ratio - 2.).abs() < 0.2);
+        }
+
+        let rk4 = |steps: usize, munthe_kaas: bool| {
+            let dt = duration / steps as f64;
+            let X = (0..steps).fold(X_0_b, |X, k| {
+                let t = k as f64 * dt;
+                if munthe_kaas {
+                    sva::transform_munthe_kaas_rk4_step(&X, t, dt, sva::VelocityFrame::Body, field)
+                } else {
+                    sva::transform_rk4_step(&X, t, dt, sva::VelocityFrame::Body, field)
+                }
+            });
+            assert!(
+                (X.rotation().matrix() * X.rotation().transpose().matrix() - sva::Mat3::identity())
+                    .norm()
+                    < TOL
+            );
+            error(&X)
+        };
+        for munthe_kaas in &[false, true] {
+            let ratio = rk4(10, *munthe_kaas) / rk4(20, *munthe_kaas);
+            assert!((ratio - 16.).abs() < 3.);
+        }
+    }
+
+    #[test]
+    fn configuration_integration_test() {
+        let mb = random_multibody();
+        let q_init = random_configuration(&mb);
+        let alpha_init = sva::VecX::new_random(mb.nr_dof());
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+        let duration = 0.1;
+        let acceleration = |_: f64, q: &sva::VecX, alpha: &sva::VecX| {
+            sva::forward_dynamics(&mb, q, alpha, &sva::VecX::zeros(mb.nr_dof()), &gravity)
+        };
+
+        let rk4 = |steps: usize| {
+            let dt = duration / steps as f64;
+            (0..steps).fold((q_init.clone(), alpha_init.clone()), |(q, alpha), k| {
+                sva::configuration_rk4_step(&mb, k as f64 * dt, &q, &alpha, dt, acceleration)
+            })
+        };
+        let euler = |steps: usize, semi_implicit: bool| {
+            let dt = duration / steps as f64;
+            (0..steps).fold((q_init.clone(), alpha_init.clone()), |(q, alpha), k| {
+                let alpha_dot = acceleration(k as f64 * dt, &q, &alpha);
+                if semi_implicit {
+                    sva::configuration_semi_implicit_euler_step(&mb, &q, &alpha, &alpha_dot, dt)
+                } else {
+                    sva::configuration_euler_step(&mb, &q, &alpha, &alpha_dot, dt)
+                }
+            })
+        };
+
+        let (q_ref, alpha_ref) = rk4(40);
+        let poses_ref = sva::forward_kinematics(&mb, &q_ref);
+        let error = |(q, alpha): (sva::VecX, sva::VecX)| {
+            let poses = sva::forward_kinematics(&mb, &q);
+            poses
+                .iter()
+                .zip(&poses_ref)
+                .map(|(pose, pose_ref)| sva::transform_error(pose, pose_ref).vector().norm())
+                .sum::<f64>()
+                + (alpha - &alpha_ref).norm()
+        };
+
+        // quaternions remain normalized
+        let (q, _) = rk4(5);
+        for (i, joint) in mb.joints.iter().enumerate() {
+            if let sva::JointType::Spherical | sva::JointType::Free = joint {
+                let offset = mb.params_offset(i);
+                assert!((q.rows(offset, 4).norm() - 1.).abs() < TOL);
+            }
+        }
+
+        let ratio = error(rk4(5)) / error(rk4(10));
+        assert!((ratio - 16.).abs() < 3.);
+        for semi_implicit in &[false, true] {
+            let ratio = error(euler(25, *semi_implicit)) / error(euler(50, *semi_implicit));
+            assert!((ratio - 2.).abs() < 0.2);
+        }
+    }
+
+    #[test]
+    fn energy_test() {
+        let mb = random_multibody();
+        let q = random_configuration(&mb);
+        let alpha = sva::VecX::new_random(mb.nr_dof());
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+
+        let mass_matrix = sva::mass_matrix(&mb, &q);
+        let kinetic = 0.5 * alpha.dot(&(&mass_matrix * &alpha));
+        assert!((sva::tree_kinetic_energy(&mb, &q, &alpha) - kinetic).abs() < TOL);
+
+        // lifting the whole tree by dz increases the potential energy by m * g * dz
+        let mut lifted = mb.clone();
+        lifted.transforms[0] = mb.transforms[0] * sva::PTransform::from_vec(sva::Vec3::z() * 0.5);
+        let lift = sva::tree_potential_energy(&lifted, &q, &gravity)
+            - sva::tree_potential_energy(&mb, &q, &gravity);
+        assert!((lift - sva::total_mass(&mb.bodies) * 9.81 * 0.5).abs() < TOL);
+
+        // the kinetic energy varies with the power of the external forces
+        let forces: Vec<sva::ForceVector> = (0..mb.nr_bodies())
+            .map(|_| sva::ForceVector::from_vector(sva::Vec6::new_random()))
+            .collect();
+        let torques = forces.iter().enumerate().fold(
+            sva::VecX::zeros(mb.nr_dof()),
+            |torques, (body, force)| {
+                let jac = sva::body_jacobian(&mb, &q, body);
+                torques
+                    + sva::VecX::from_iterator(
+                        mb.nr_dof(),
+                        (jac.transpose() * force.vector()).iter().cloned(),
+                    )
+            },
+        );
+        let no_gravity = sva::Vec3::zeros();
+        let acceleration = |_: f64, q: &sva::VecX, alpha: &sva::VecX| {
+            sva::forward_dynamics(&mb, q, alpha, &torques, &no_gravity)
+        };
+        let dt = 1e-4;
+        let (q_next, alpha_next) =
+            sva::configuration_rk4_step(&mb, 0., &q, &alpha, dt, acceleration);
+        let (q_prev, alpha_prev) =
+            sva::configuration_rk4_step(&mb, 0., &q, &alpha, -dt, acceleration);
+        let energy_rate = (sva::tree_kinetic_energy(&mb, &q_next, &alpha_next)
+            - sva::tree_kinetic_energy(&mb, &q_prev, &alpha_prev))
+            / (2. * dt);
+        let velocities = sva::forward_velocity(&mb, &q, &alpha);
+        assert!((energy_rate - sva::power(&forces, &velocities)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn pendulum_energy_drift_test() {
+        // passive chain of 4 links hanging along -z and swinging around y
+        let nr_links = 4;
+        let link = sva::RBInertia::from_com_inertia(
+            1.,
+            sva::Vec3::new(0., 0., -0.25),
+            sva::Mat3::from_diagonal(&sva::Vec3::new(0.02, 0.02, 0.001)),
+        );
+        let mb = sva::MultiBody::new(
+            vec![link; nr_links],
+            vec![sva::JointType::Revolute(sva::Vec3::y()); nr_links],
+            (0..nr_links).map(|i| i.checked_sub(1)).collect(),
+            (0..nr_links)
+                .map(|i| {
+                    sva::PTransform::from_vec(sva::Vec3::new(
+                        0.,
+                        0.,
+                        if i == 0 { 0. } else { -0.5 },
+                    ))
+                })
+                .collect(),
+        );
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+        let zero_torques = sva::VecX::zeros(nr_links);
+        let acceleration = |_: f64, q: &sva::VecX, alpha: &sva::VecX| {
+            sva::forward_dynamics(&mb, q, alpha, &zero_torques, &gravity)
+        };
+
+        let mut q = sva::VecX::from_element(nr_links, 0.4);
+        let mut alpha = sva::VecX::zeros(nr_links);
+        let initial_energy = sva::tree_energy(&mb, &q, &alpha, &gravity);
+        let dt = 1e-2;
+        for k in 0..200 {
+            let (q_next, alpha_next) =
+                sva::configuration_rk4_step(&mb, k as f64 * dt, &q, &alpha, dt, acceleration);
+            q = q_next;
+            alpha = alpha_next;
+        }
+        let drift = (sva::tree_energy(&mb, &q, &alpha, &gravity) - initial_energy).abs();
+        assert!(drift < 1e-4);
+        // the chain actually moved
+        assert!(sva::tree_kinetic_energy(&mb, &q, &alpha) > 0.1);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn screw_test() {
+        let x_axis = sva::PluckerLine::from_point_direction(&sva::Vec3::zeros(), &sva::Vec3::x());
+        let y_line = sva::PluckerLine::from_point_direction(
+            &sva::Vec3::new(3., 0., 2.),
+            &sva::Vec3::new(0., 2., 0.),
+        );
+        assert!((x_axis.distance(&y_line) - 2.).abs() < TOL);
+        assert!((x_axis.angle(&y_line) - f64::consts::FRAC_PI_2).abs() < TOL);
+        assert!((y_line.point() - sva::Vec3::new(3., 0., 2.)).norm() < TOL);
+        let parallel =
+            sva::PluckerLine::from_point_direction(&sva::Vec3::new(5., 1., 1.), &-sva::Vec3::x());
+        assert!((x_axis.distance(&parallel) - 2f64.sqrt()).abs() < TOL);
+        assert!((x_axis.angle(&parallel) - f64::consts::PI).abs() < TOL);
+        assert!(x_axis.reciprocal_product(&parallel).abs() < TOL);
+        assert_eq!(
+            sva::PluckerLine::from_plucker(&sva::Vec3::x(), &sva::Vec3::x()),
+            None
+        );
+
+        // distances and angles do not depend on the frame
+        let X_a_b = random_transform();
+        let (x_b, y_b) = (x_axis.transform(&X_a_b), y_line.transform(&X_a_b));
+        assert!((x_b.distance(&y_b) - 2.).abs() < TOL);
+        assert!((x_b.angle(&y_b) - f64::consts::FRAC_PI_2).abs() < TOL);
+        // the x axis passes through the origin of frame a
+        let origin_a = X_a_b.rotation() * -X_a_b.translation();
+        assert!(x_b.distance_to_point(&origin_a) < TOL);
+
+        // twist and wrench decompositions
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let (screw, magnitude) = sva::Screw::from_motion(&mv).unwrap();
+        assert!((screw.motion(magnitude) - mv).vector().norm() < TOL);
+        assert!((magnitude - mv.angular.norm()).abs() < TOL);
+        let isa = sva::instantaneous_screw_axis(&mv).unwrap();
+        // the velocity of the points on the axis is along the axis
+        let point_velocity = mv.linear + mv.angular.cross(&isa.axis.point());
+        assert!(point_velocity.cross(&isa.axis.direction()).norm() < TOL);
+
+        let fv = sva::ForceVector::from_vector(sva::Vec6::new_random());
+        let (wrench_screw, force) = sva::Screw::from_force(&fv).unwrap();
+        assert!((wrench_screw.force(force) - fv).vector().norm() < TOL);
+
+        let transformed = screw.transform(&X_a_b);
+        assert!((transformed.motion(magnitude) - X_a_b * mv).vector().norm() < TOL);
+        assert!((transformed.pitch - screw.pitch).abs() < TOL);
+
+        // pure translations have an infinite pitch
+        let translation =
+            sva::MotionVector::from_vectors(sva::Vec3::zeros(), sva::Vec3::new(0., 3., 4.));
+        let (slide, speed) = sva::Screw::from_motion(&translation).unwrap();
+        assert!(slide.is_infinite_pitch());
+        assert!((speed - 5.).abs() < TOL);
+        assert!((slide.motion(speed) - translation).vector().norm() < TOL);
+        assert!(
+            (slide.transform(&X_a_b).motion(speed) - X_a_b * translation)
+                .vector()
+                .norm()
+                < TOL
+        );
+        assert_eq!(sva::Screw::from_motion(&sva::MotionVector::zero()), None);
+
+        // a revolute joint cannot produce work against forces through its axis
+        let revolute = sva::Screw::new(x_axis, 0.);
+        let through_axis = sva::PluckerLine::from_point_direction(
+            &sva::Vec3::new(1., 0., 0.),
+            &sva::Vec3::new(0., 1., 1.),
+        );
+        assert!(revolute.reciprocal_product(&through_axis.force()).abs() < TOL);
+        assert!(revolute.reciprocal_product(&parallel.force()).abs() < TOL);
+        assert!((revolute.reciprocal_product(&y_line.force()) + 2.).abs() < TOL);
+    }
+
+    #[test]
+    fn ad_test() {
+        use sva::SpatialOperator;
+
+        let u = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let v = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let w = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let f = sva::ForceVector::from_vector(sva::Vec6::new_random());
+        let rbi = random_rb_inertia();
+
+        assert!(((sva::ad(u) * v).vector() - sva::ad(u).matrix() * v.vector()).norm() < TOL);
+        assert!(
+            ((sva::ad_dual(u) * f).vector() - sva::ad_dual(u).matrix() * f.vector()).norm() < TOL
+        );
+        assert!((sva::ad_dual(u).matrix() + sva::ad(u).matrix().transpose()).norm() < TOL);
+        assert_eq!(sva::ad(u).dual(), sva::ad_dual(u));
+
+        // compositions and the Lie bracket
+        let uv = sva::ad(u) * sva::ad(v);
+        assert!((uv.matrix() - sva::ad(u).matrix() * sva::ad(v).matrix()).norm() < TOL);
+        let bracket = uv * w - (sva::ad(v) * sva::ad(u)) * w;
+        assert!((bracket - sva::ad(u.cross(v)) * w).vector().norm() < TOL);
+        let dual_uv = sva::ad_dual(u) * sva::ad_dual(v);
+        assert!(((dual_uv * f).vector() - dual_uv.matrix() * f.vector()).norm() < TOL);
+
+        // operators acting on inertias
+        let momentum_rate = sva::ad_dual(v) * rbi;
+        assert!((momentum_rate * w - v.cross_dual(rbi * w)).vector().norm() < TOL);
+        assert!((momentum_rate.matrix() * w.vector() - (momentum_rate * w).vector()).norm() < TOL);
+        let inertia_ad = rbi * sva::ad(v);
+        assert!((inertia_ad * w - rbi * v.cross(w)).vector().norm() < TOL);
+        let nested = sva::compose(sva::ad_dual(u), inertia_ad);
+        assert!(((nested * w).vector() - nested.matrix() * w.vector()).norm() < TOL);
+
+        // the Coriolis matrix reproduces the velocity dependent joint forces
+        let mb = random_multibody();
+        let q = random_configuration(&mb);
+        let alpha = sva::VecX::new_random(mb.nr_dof());
+        let zero = sva::VecX::zeros(mb.nr_dof());
+        let bias = sva::inverse_dynamics(&mb, &q, &alpha, &zero, &sva::Vec3::zeros());
+        assert!((sva::coriolis_matrix(&mb, &q, &alpha) * &alpha - bias).norm() < TOL);
+    }
+
+    #[test]
+    fn force_motion_pairing_test() {
+        let m = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let m2 = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let f = sva::ForceVector::from_vector(sva::Vec6::new_random());
+
+        assert!((f.dot(m) - m.dot(f)).abs() < TOL);
+        assert!((f * m - m.dot(f)).abs() < TOL);
+        assert!((m * f - f.vector().dot(&m.vector())).abs() < TOL);
+
+        // duality identity (m x* f) . m2 = -f . (m x m2)
+        assert!((m.cross_dual(f).dot(m2) + f.dot(m.cross(m2))).abs() < TOL);
+        assert!((f.cross(m) + m.cross_dual(f)).vector().norm() < TOL);
+        // a body never does work on itself through its own velocity
+        assert!(m.cross_dual(f).dot(m).abs() < TOL);
+
+        let impedance = sva::ImpedanceVector::from_scalars(2., 3.);
+        let expected = (2. * m.angular.norm_squared() + 3. * m.linear.norm_squared()).sqrt();
+        assert!((m.weighted_norm(&impedance) - expected).abs() < TOL);
+        assert!(
+            (m.metric_norm(&sva::ImpedanceMatrix::try_from(impedance).unwrap()) - expected).abs()
+                < TOL
+        );
+        let admittance = sva::AdmittanceVector::from_scalars(0.5, 4.);
+        let expected = (0.5 * f.couple.norm_squared() + 4. * f.force.norm_squared()).sqrt();
+        assert!((f.weighted_norm(&admittance) - expected).abs() < TOL);
+        assert!(
+            (f.metric_norm(&sva::AdmittanceMatrix::try_from(admittance).unwrap()) - expected).abs()
+                < TOL
+        );
+    }
+
+    #[test]
+    fn vector_interop_test() {
+        let array = [1., 2., 3., 4., 5., 6.];
+        let vec6 = sva::Vec6::from_row_slice(&array);
+
+        let mv = sva::MotionVector::from(array);
+        assert_eq!(mv, sva::MotionVector::from(vec6));
+        assert_eq!(sva::Vec6::from(mv), vec6);
+        assert_eq!(mv.as_ref(), &array[..]);
+        assert_eq!(mv[4], 5.);
+        let fv: sva::ForceVector = vec6.into();
+        assert_eq!(fv.as_ref(), &array[..]);
+        assert_eq!(fv[0], 1.);
+        let iv = sva::ImpedanceVector::from(array);
+        assert_eq!(iv.as_ref(), &array[..]);
+        let av = sva::AdmittanceVector::from(array);
+        assert_eq!(av.as_ref(), &array[..]);
+        assert_eq!(std::mem::size_of::<sva::MotionVector>(), 6 * 8);
+
+        // middleware buffers holding several vectors
+        let mut buffer = [0.; 12];
+        mv.copy_to_slice(&mut buffer[..6]);
+        fv.copy_to_slice(&mut buffer[6..]);
+        assert_eq!(sva::MotionVector::from_slice(&buffer[..6]), mv);
+        assert_eq!(sva::ForceVector::from_slice(&buffer[6..]), fv);
+        iv.copy_to_slice(&mut buffer[..6]);
+        assert_eq!(sva::ImpedanceVector::from_slice(&buffer[..6]), iv);
+        av.copy_to_slice(&mut buffer[6..]);
+        assert_eq!(sva::AdmittanceVector::from_slice(&buffer[6..]), av);
+
+        let transform = random_transform();
+        assert!((transform.matrix() * mv - transform * mv).vector().norm() < TOL);
+        assert!(
+            (transform.dual_matrix() * fv - transform.dual_mul(&fv))
+                .vector()
+                .norm()
+                < TOL
+        );
+    }
+
+    #[test]
+    fn batch_test() {
+        let transform = random_transform();
+        let motions: Vec<sva::MotionVector> = (0..37)
+            .map(|_| sva::MotionVector::from_vector(sva::Vec6::new_random()))
+            .collect();
+        let forces: Vec<sva::ForceVector> = (0..37)
+            .map(|_| sva::ForceVector::from_vector(sva::Vec6::new_random()))
+            .collect();
+        let motion_batch = sva::MotionVectorBatch::from_vectors(&motions);
+        let force_batch = sva::ForceVectorBatch::from_vectors(&forces);
+        assert_eq!(motion_batch.len(), 37);
+        assert_eq!(motion_batch.to_vectors(), motions);
+        assert_eq!(force_batch.force(1)[5], forces[5].force[1]);
+
+        let check_motions =
+            |batch: &sva::MotionVectorBatch,
+             expected: &dyn Fn(&sva::MotionVector) -> sva::MotionVector| {
+                assert_eq!(batch.len(), motions.len());
+                for (i, mv) in motions.iter().enumerate() {
+                    assert!((batch.get(i) - expected(mv)).vector().norm() < TOL);
+                }
+            };
+        check_motions(&transform.batch_mul(&motion_batch), &|mv| transform * *mv);
+        check_motions(&transform.batch_inv_mul(&motion_batch), &|mv| {
+            transform.inv_mul(mv)
+        });
+
+        let check_forces =
+            |batch: &sva::ForceVectorBatch,
+             expected: &dyn Fn(&sva::ForceVector) -> sva::ForceVector| {
+                assert_eq!(batch.len(), forces.len());
+                for (i, fv) in forces.iter().enumerate() {
+                    assert!((batch.get(i) - expected(fv)).vector().norm() < TOL);
+                }
+            };
+        check_forces(&transform.batch_dual_mul(&force_batch), &|fv| {
+            transform.dual_mul(fv)
+        });
+        check_forces(&transform.batch_trans_mul(&force_batch), &|fv| {
+            transform.trans_mul(fv)
+        });
+
+        // output buffers are reused and resized
+        let mut output = sva::MotionVectorBatch::from_vectors(&motions[..3]);
+        transform.batch_mul_into(&motion_batch, &mut output);
+        check_motions(&output, &|mv| transform * *mv);
+        let mut empty = motion_batch.clone();
+        empty.clear();
+        transform.batch_mul_into(&empty, &mut output);
+        assert!(output.is_empty());
+
+        let mut batch = motion_batch.clone();
+        batch.set(3, &sva::MotionVector::zero());
+        assert_eq!(batch.get(3), sva::MotionVector::zero());
+    }
+
+    #[test]
+    fn workspace_test() {
+        let mb = random_multibody();
+        let mut workspace = sva::MultiBodyWorkspace::new();
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+
+        // a reused workspace gives the same results as fresh allocations
+        for _ in 0..3 {
+            let q = random_configuration(&mb);
+            let alpha = sva::VecX::new_random(mb.nr_dof());
+            let alpha_dot = sva::VecX::new_random(mb.nr_dof());
+            assert!(workspace.forward_kinematics(&mb, &q) == &sva::forward_kinematics(&mb, &q)[..]);
+            assert!(workspace.body_jacobian(&mb, &q, 4) == sva::body_jacobian(&mb, &q, 4));
+            assert!(
+                workspace.inverse_dynamics(&mb, &q, &alpha, &alpha_dot, &gravity)
+                    == sva::inverse_dynamics(&mb, &q, &alpha, &alpha_dot, &gravity)
+            );
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_test() {
+        let mb = random_multibody();
+        let gravity = sva::Vec3::new(0., 0., -9.81);
+        let states: Vec<_> = (0..64)
+            .map(|_| {
+                (
+                    random_configuration(&mb),
+                    sva::VecX::new_random(mb.nr_dof()),
+                    sva::VecX::new_random(mb.nr_dof()),
+                )
+            })
+            .collect();
+        let qs: Vec<_> = states.iter().map(|state| state.0.clone()).collect();
+
+        // results are identical to the sequential ones and in the input order
+        let poses = sva::par_forward_kinematics(&mb, &qs);
+        let jacobians = sva::par_body_jacobian(&mb, &qs, 4);
+        let mass_matrices = sva::par_mass_matrix(&mb, &qs);
+        let torques = sva::par_inverse_dynamics(&mb, &states, &gravity);
+        let velocity_states: Vec<_> = states
+            .iter()
+            .map(|(q, alpha, _)| (q.clone(), alpha.clone()))
+            .collect();
+        let velocities = sva::par_forward_velocity(&mb, &velocity_states);
+        assert!(poses.len() == qs.len());
+        for (i, (q, alpha, alpha_dot)) in states.iter().enumerate() {
+            assert!(poses[i] == sva::forward_kinematics(&mb, q));
+            assert!(velocities[i] == sva::forward_velocity(&mb, q, alpha));
+            assert!(jacobians[i] == sva::body_jacobian(&mb, q, 4));
+            assert!(mass_matrices[i] == sva::mass_matrix(&mb, q));
+            assert!(torques[i] == sva::inverse_dynamics(&mb, q, alpha, alpha_dot, &gravity));
+        }
+
+        let accelerations = sva::par_forward_dynamics(&mb, &states, &gravity);
+        for ((q, alpha, torque), alpha_dot) in states.iter().zip(&accelerations) {
+            let expected = sva::forward_dynamics(&mb, q, alpha, torque, &gravity);
+            assert!((alpha_dot - expected).norm() < TOL);
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn dual_quat_test() {
+        let close =
+            |a: &sva::PTransform, b: &sva::PTransform| (a.matrix() - b.matrix()).norm() < TOL;
+        let X_a_b = random_transform();
+        let X_b_c = random_transform();
+        let dq_a_b = sva::DualQuat::from(X_a_b);
+        let dq_b_c = sva::DualQuat::from(X_b_c);
+
+        // round trip and equivalence of the group operations
+        assert!(close(&sva::PTransform::from(dq_a_b), &X_a_b));
+        assert!((dq_a_b.translation() - X_a_b.translation()).norm() < TOL);
+        assert!(close(&(dq_b_c * dq_a_b).to_ptransform(), &(X_b_c * X_a_b)));
+        assert!(close(&dq_a_b.inv().to_ptransform(), &X_a_b.inv()));
+        assert!(close(&(-dq_a_b).to_ptransform(), &X_a_b));
+
+        let mut scaled = dq_a_b;
+        scaled.real *= 2.;
+        scaled.dual = scaled.dual * 2. + scaled.real * 0.1;
+        assert!(close(&scaled.normalize().to_ptransform(), &X_a_b));
+
+        // action on points and spatial vectors
+        let p_a = sva::Vec3::new_random();
+        let p_b = X_a_b.rotation() * (p_a - X_a_b.translation());
+        assert!((dq_a_b.transform_point(&p_a) - p_b).norm() < TOL);
+        let mv = sva::MotionVector::from_vector(sva::Vec6::new_random());
+        let fv = sva::ForceVector::from_vector(sva::Vec6::new_random());
+        assert!(((dq_a_b * mv).vector() - (X_a_b * mv).vector()).norm() < TOL);
+        assert!((dq_a_b.inv_mul(&mv).vector() - X_a_b.inv_mul(&mv).vector()).norm() < TOL);
+        assert!((dq_a_b.dual_mul(&fv).vector() - X_a_b.dual_mul(&fv).vector()).norm() < TOL);
+        assert!((dq_a_b.trans_mul(&fv).vector() - X_a_b.trans_mul(&fv).vector()).norm() < TOL);
+
+        // ScLERP follows the screw motion given by transform_exp
+        let twist =
+            sva::MotionVector::from_vectors(sva::Vec3::new(0.3, -1., 0.5), sva::Vec3::new_random());
+        let X_end = sva::transform_exp(&twist) * X_a_b;
+        let dq_end = sva::DualQuat::from(X_end);
+        for &t in &[0., 0.25, 0.5, 1.] {
+            let expected = sva::transform_exp(&(twist * t)) * X_a_b;
+            assert!(close(
+                &sva::sclerp(&dq_a_b, &dq_end, t).to_ptransform(),
+                &expected
+            ));
+        }
+        // pure translations
+        let X_trans = sva::PTransform::from_vec(sva::Vec3::new_random()) * X_a_b;
+        let half = sva::sclerp(&dq_a_b, &sva::DualQuat::from(X_trans), 0.5);
+        assert!(
+            (half.translation() - (X_a_b.translation() + X_trans.translation()) * 0.5).norm() < TOL
+        );
+
+        // linear blending
+        let blend = sva::dual_quat_blend(&[dq_a_b, -dq_a_b], &[0.3, 0.7]).unwrap();
+        assert!(close(&blend.to_ptransform(), &X_a_b));
+        let blend = sva::dual_quat_blend(&[dq_a_b, dq_end], &[1., 0.]).unwrap();
+        assert!(close(&blend.to_ptransform(), &X_a_b));
+        let blend = sva::dual_quat_blend(&[dq_a_b, dq_end], &[0.5, 0.5]).unwrap();
+        let blend_transform = blend.to_ptransform();
+        assert!(close(
+            &sva::DualQuat::from(blend_transform).to_ptransform(),
+            &blend_transform
+        ));
+        assert!(sva::dual_quat_blend(&[], &[]).is_none());
+        assert!(sva::dual_quat_blend(&[dq_a_b, dq_a_b], &[1., -1.]).is_none());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn planar_test() {
+        use sva::planar;
+
+        let random_transform = || {
+            planar::PTransform::from_mat_vec(
+                planar::rot(sva::Vec2::new_random()[0] * 3.),
+                sva::Vec2::new_random(),
+            )
+        };
+        let X_a_b = random_transform();
+        let X_b_c = random_transform();
+        let mv = planar::MotionVector::from_vector(sva::Vec3::new_random());
+        let mv2 = planar::MotionVector::from_vector(sva::Vec3::new_random());
+        let fv = planar::ForceVector::from_vector(sva::Vec3::new_random());
+        let inertia = planar::RBInertia::from_com_inertia(2., sva::Vec2::new_random(), 0.5);
+
+        // embedding and projection are inverse of each other
+        assert!(planar::MotionVector::project(&mv.embed()) == mv);
+        assert!(planar::ForceVector::project(&fv.embed()) == fv);
+        assert!(
+            (planar::PTransform::project(&X_a_b.embed()).matrix() - X_a_b.matrix()).norm() < TOL
+        );
+        assert!(
+            (planar::RBInertia::project(&inertia.embed()).matrix() - inertia.matrix()).norm() < TOL
+        );
+        assert!((inertia.com_inertia() - 0.5).abs() < TOL);
+
+        // the planar operations match the 6D ones
+        let X = X_a_b.embed();
+        let close_mv = |a: planar::MotionVector, b: sva::MotionVector| {
+            (a.vector() - planar::MotionVector::project(&b).vector()).norm() < TOL
+        };
+        let close_fv = |a: planar::ForceVector, b: sva::ForceVector| {
+            (a.vector() - planar::ForceVector::project(&b).vector()).norm() < TOL
+        };
+        assert!(close_mv(X_a_b * mv, X * mv.embed()));
+        assert!(close_mv(X_a_b.inv_mul(&mv), X.inv_mul(&mv.embed())));
+        assert!(close_fv(X_a_b.dual_mul(&fv), X.dual_mul(&fv.embed())));
+        assert!(close_fv(X_a_b.trans_mul(&fv), X.trans_mul(&fv.embed())));
+        assert!(close_mv(mv.cross(mv2), mv.embed().cross(mv2.embed())));
+        assert!(close_fv(
+            mv.cross_dual(fv),
+            mv.embed().cross_dual(fv.embed())
+        ));
+        assert!(close_fv(fv.cross(mv), fv.embed().cross(mv.embed())));
+        assert!((mv * fv - mv.embed() * fv.embed()).abs() < TOL);
+        assert!(close_fv(inertia * mv, inertia.embed() * mv.embed()));
+
+        let X_a_c = (X_b_c * X_a_b).embed();
+        let X_a_c_expected = X_b_c.embed() * X;
+        assert!((X_a_c.matrix() - X_a_c_expected.matrix()).norm() < TOL);
+        assert!((X_a_b.inv().embed().matrix() - X.inv().matrix()).norm() < TOL);
+
+        let inertia_b = X_a_b.inertia_dual_mul(&inertia);
+        let expected = planar::RBInertia::project(&X.inertia_dual_mul(&inertia.embed()));
+        assert!((inertia_b.matrix() - expected.matrix()).norm() < TOL);
+        let inertia_a = X_a_b.inertia_trans_mul(&inertia_b);
+        assert!((inertia_a.matrix() - inertia.matrix()).norm() < TOL);
+
+        // matrices act as the operators
+        assert!(((X_a_b * mv).vector() - X_a_b.matrix() * mv.vector()).norm() < TOL);
+        assert!((X_a_b.dual_mul(&fv).vector() - X_a_b.dual_matrix() * fv.vector()).norm() < TOL);
+        assert!(((inertia * mv).vector() - inertia.matrix() * mv.vector()).norm() < TOL);
+    }
+
+    #[test]
+    #[should_panic]
+    fn impedance_matrix_negative_scalar_test() {
+        let z = sva::ImpedanceMatrix::try_from(sva::ImpedanceVector::from_scalars(1., 2.)).unwrap();
+        let _ = z * -1.;
+    }
+
+    #[test]
+    fn component_error_test() {
+        let errors: Vec<Box<dyn std::error::Error>> = vec![
+            Box::new(sva::ZeroComponentError),
+            Box::new(sva::NegativeComponentError),
+        ];
+        for error in errors {
+            assert!(!error.to_string().is_empty());
+        }
+    }
 }